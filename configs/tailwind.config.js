@@ -1,5 +1,28 @@
+// Colors are CSS variables so they can be switched by a theme class
+// on the root element (see `css/themes.css`).
+const shades = [100, 200, 300, 400, 500, 600, 700, 800, 900];
+
+const themeColor = name =>
+  shades.reduce((color, shade) => {
+    color[shade] = `var(--color-${name}-${shade})`;
+    return color;
+  }, {});
+
 module.exports = {
-  theme: {},
+  theme: {
+    colors: {
+      transparent: "transparent",
+      current: "currentColor",
+      black: "var(--color-black)",
+      white: "var(--color-white)",
+      gray: themeColor("gray"),
+      red: themeColor("red"),
+      orange: themeColor("orange"),
+      yellow: themeColor("yellow"),
+      green: themeColor("green"),
+      blue: themeColor("blue")
+    }
+  },
   variants: {},
   plugins: []
-};
\ No newline at end of file
+};
@@ -1,3 +1,4 @@
+const fs = require("fs");
 const path = require("path");
 const dist = path.resolve(__dirname, "../dist");
 
@@ -8,6 +9,33 @@ const CopyWebpackPlugin = require("copy-webpack-plugin");
 const { CleanWebpackPlugin } = require("clean-webpack-plugin");
 const MiniCssExtractPlugin = require("mini-css-extract-plugin");
 
+// Emits `service-worker.js` from `entries/service-worker.js`
+// with the list of all emitted assets (the app shell) to precache.
+class ServiceWorkerPlugin {
+  apply(compiler) {
+    compiler.hooks.emit.tap("ServiceWorkerPlugin", compilation => {
+      const appShell = Object.keys(compilation.assets)
+        // EPUBs are downloads, OpenGraph images are for link previews
+        // and the feed is for feed readers, they aren't a part of the app shell.
+        .filter(asset =>
+          !asset.endsWith(".map") &&
+          !asset.endsWith(".epub") &&
+          !asset.endsWith(".atom") &&
+          !asset.startsWith("og/")
+        )
+        .map(asset => `/${asset}`);
+      const source = fs
+        .readFileSync(path.resolve(__dirname, "../entries/service-worker.js"), "utf8")
+        .replace("__CACHE_VERSION__", compilation.hash)
+        .replace("__APP_SHELL__", JSON.stringify(appShell));
+      compilation.assets["service-worker.js"] = {
+        source: () => source,
+        size: () => source.length
+      };
+    });
+  }
+}
+
 module.exports = (env, argv) => {
   return {
   // Don't show `asset size limit: The following asset(s) exceed the recommended size limit (244 KiB).`
@@ -56,16 +84,34 @@ module.exports = (env, argv) => {
 
       // You can find files from folder `../static` on url `http://my-site.com/static/`.
       // And favicons in the root.
+      // EPUBs generated by `crate/build.rs` on url `http://my-site.com/epub/`.
       new CopyWebpackPlugin([
         {
           from: "static",
           to: "static"
         },
+        {
+          from: "crate/generated_guides/epub",
+          to: "epub"
+        },
+        {
+          // OpenGraph images of guides rendered by `build.rs`.
+          context: "crate/generated_guides",
+          from: "*/*.png",
+          to: "og"
+        },
+        {
+          // The Atom feed of releases generated by `build.rs`.
+          from: "crate/generated_guides/changelog.atom",
+          to: ""
+        },
         {
           from: "favicons",
           to: ""
         }
       ]),
+      // Has to be after plugins emitting assets to see all of them.
+      new ServiceWorkerPlugin(),
     ],
     // Webpack try to guess how to resolve imports in this order:
     resolve: {
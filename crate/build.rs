@@ -1,40 +1,361 @@
+#[path = "build/api_check.rs"]
+mod api_check;
+#[path = "build/api_items.rs"]
+mod api_items;
+#[path = "build/changelog.rs"]
+mod changelog;
+#[path = "src/code_block.rs"]
+mod code_block;
+#[path = "build/date.rs"]
+mod date;
+#[path = "build/epub.rs"]
+mod epub;
+#[path = "build/glossary.rs"]
+mod glossary;
+#[path = "build/highlight.rs"]
+mod highlight;
+#[path = "build/og_image.rs"]
+mod og_image;
+
+use api_items::ApiItems;
 use pulldown_cmark::{self, CodeBlockKind, Event, Tag};
 use std::{
     cell::RefCell,
-    fs,
+    collections::{BTreeSet, HashMap},
+    env, fs,
     path::{Path, PathBuf},
     rc::Rc,
 };
 use uuid::Uuid;
 
+const SITE_URL: &str = "https://seed-rs.org";
+/// The Seed changelog is a guide of the last version that had it.
+const CHANGELOG_PATH: &str = "guides/0.6.0/changelog.md";
+const GLOSSARY_PATH: &str = "glossary.md";
+const EPUB_FOLDER: &str = "generated_guides/epub";
+const FRONT_MATTER_DELIMITER: &str = "---\n";
+/// Search engines show about 160 characters.
+const DESCRIPTION_MAX_CHARS: usize = 160;
+/// Inputs of the build script. Without them Cargo reruns it
+/// on every change in the package, including the files it generates.
+const RERUN_IF_CHANGED: &[&str] = &[
+    "build.rs",
+    "build",
+    "guides",
+    GLOSSARY_PATH,
+    "api_items",
+    "src/code_block.rs",
+    "src/guide.rs",
+    "../seed_branding",
+];
+
 fn main() {
-    for path in html_and_text_files() {
+    for path in RERUN_IF_CHANGED {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+
+    for path in generated_guide_files() {
         fs::remove_file(path).unwrap();
     }
 
+    let mut vocabulary = BTreeSet::new();
+    let mut generated_guides = HashMap::new();
+    let mut api_items_by_version = HashMap::new();
+    let menu_guides = menu_guides();
+    let glossary_terms =
+        glossary::parse(&fs::read_to_string(GLOSSARY_PATH).unwrap());
+    let releases =
+        changelog::parse(&fs::read_to_string(CHANGELOG_PATH).unwrap());
+
     for path in markdown_files() {
         let markdown = fs::read_to_string(&path).unwrap();
-        let (html, text_parts) = markdown_to_html_and_text_parts(&markdown);
-
-        let parent_folder = format!(
-            "generated_guides/{}",
-            path.iter().nth_back(1).unwrap().to_str().unwrap()
+        let (front_matter, markdown) = split_front_matter(&markdown);
+        let seed_version = path.iter().nth_back(1).unwrap().to_str().unwrap();
+        let api_items = api_items_by_version
+            .entry(seed_version.to_owned())
+            .or_insert_with(|| ApiItems::load(seed_version));
+        let file_stem = path.file_stem().unwrap().to_str().unwrap();
+        let glossary_tooltips = glossary_tooltips(
+            &glossary_terms,
+            &menu_guides,
+            seed_version,
+            file_stem,
+        );
+        let (html, text_parts, headings) = markdown_to_html_and_text_parts(
+            markdown,
+            api_items.as_ref(),
+            glossary_tooltips,
         );
+        let description = front_matter
+            .and_then(|front_matter| {
+                front_matter_value(front_matter, "description")
+            })
+            .map_or_else(|| first_paragraph(markdown), ToOwned::to_owned);
+
+        let parent_folder = format!("generated_guides/{}", seed_version);
         if !Path::new(&parent_folder).exists() {
             fs::create_dir(&parent_folder).unwrap();
         }
 
-        let file_stem = path.file_stem().unwrap().to_str().unwrap();
-
         let html_path = format!("{}/{}.html", parent_folder, file_stem);
-        fs::write(html_path, html).unwrap();
+        fs::write(html_path, &html).unwrap();
+
+        let text = text_parts.join(" ");
+        vocabulary.extend(vocabulary_words(&text));
 
         let text_path = format!("{}/{}.txt", parent_folder, file_stem);
-        fs::write(text_path, text_parts.join(" ")).unwrap();
+        fs::write(text_path, text.to_lowercase()).unwrap();
+
+        let plain_text_path =
+            format!("{}/{}.plain.txt", parent_folder, file_stem);
+        fs::write(plain_text_path, text).unwrap();
+
+        let outline_path =
+            format!("{}/{}.outline.txt", parent_folder, file_stem);
+        fs::write(outline_path, outline(&headings)).unwrap();
+
+        let description_path =
+            format!("{}/{}.description.txt", parent_folder, file_stem);
+        fs::write(description_path, shorten_description(&description)).unwrap();
+
+        generated_guides.insert(
+            (seed_version.to_owned(), file_stem.to_owned()),
+            (html, headings),
+        );
+    }
+
+    let vocabulary = vocabulary.into_iter().collect::<Vec<_>>();
+    fs::write("generated_guides/vocabulary.txt", vocabulary.join("\n"))
+        .unwrap();
+
+    check_api_references(&api_items_by_version);
+    write_changelog(&releases);
+    write_glossary(&glossary_terms, &menu_guides);
+
+    write_epubs(&menu_guides, &generated_guides, &epub_modified(&releases));
+    write_og_images(&menu_guides);
+}
+
+/// Reports references in guides to Seed items missing in the guide's version
+/// but existing in another one (see `build/api_check.rs`).
+/// Warnings become errors with the feature `deny_stale_api_references`.
+fn check_api_references(
+    api_items_by_version: &HashMap<String, Option<ApiItems>>,
+) {
+    let mut reports = Vec::new();
+    for path in markdown_files() {
+        let file = fs::read_to_string(&path).unwrap();
+        let (_, markdown) = split_front_matter(&file);
+        let first_line =
+            1 + file[..file.len() - markdown.len()].matches('\n').count();
+        let seed_version = path.iter().nth_back(1).unwrap().to_str().unwrap();
+        let api_items = match api_items_by_version.get(seed_version) {
+            Some(Some(api_items)) => api_items,
+            _ => continue,
+        };
+        let other_api_items = api_items_by_version
+            .values()
+            .flatten()
+            .filter(|other| other.seed_version() != seed_version)
+            .collect::<Vec<_>>();
+        for stale in api_check::stale_references(
+            markdown,
+            first_line,
+            api_items,
+            &other_api_items,
+        ) {
+            let report = format!(
+                "{}:{}: `{}` doesn't exist in Seed {} (only in {})",
+                path.display(),
+                stale.line,
+                stale.reference,
+                seed_version,
+                stale.seed_versions.join(", "),
+            );
+            reports.push((path.clone(), stale.line, report));
+        }
+    }
+    reports.sort();
+    let reports =
+        reports.into_iter().map(|(_, _, report)| report).collect::<Vec<_>>();
+
+    if env::var_os("CARGO_FEATURE_DENY_STALE_API_REFERENCES").is_some()
+        && !reports.is_empty()
+    {
+        panic!("Stale API references in guides:\n{}", reports.join("\n"));
+    }
+    for report in reports {
+        println!("cargo:warning={}", report);
     }
 }
 
-fn html_and_text_files() -> Vec<PathBuf> {
+/// Writes releases for the changelog page and their Atom feed.
+fn write_changelog(releases: &[changelog::Release]) {
+    fs::write("generated_guides/changelog.txt", changelog::tsv(releases))
+        .unwrap();
+    fs::write(
+        "generated_guides/changelog.atom",
+        changelog::atom_feed(releases),
+    )
+    .unwrap();
+}
+
+/// Writes glossary terms for the glossary page.
+/// Terms link to guides by slugs of the newest Seed version with the guide.
+fn write_glossary(terms: &[glossary::Term], menu_guides: &[MenuGuide]) {
+    let tsv = glossary::tsv(terms, |term| {
+        menu_guides
+            .iter()
+            .rev()
+            .find(|guide| guide.file_name == term.guide)
+            .unwrap_or_else(|| {
+                panic!("Glossary guide `{}` doesn't exist", term.guide)
+            })
+            .slug
+            .clone()
+    });
+    fs::write("generated_guides/glossary.txt", tsv).unwrap();
+}
+
+/// Tooltips of glossary terms explained in guides of the Seed version.
+/// Terms aren't wrapped in the guide that explains them.
+fn glossary_tooltips<'a>(
+    terms: &'a [glossary::Term],
+    menu_guides: &[MenuGuide],
+    seed_version: &str,
+    file_name: &str,
+) -> Vec<glossary::Tooltip<'a>> {
+    terms
+        .iter()
+        .filter(|term| term.guide != file_name)
+        .filter_map(|term| {
+            let guide = menu_guides.iter().find(|guide| {
+                guide.seed_version == seed_version
+                    && guide.file_name == term.guide
+            })?;
+            let mut guide_url = format!("/{}/{}", seed_version, guide.slug);
+            if let Some(heading_id) = &term.heading_id {
+                guide_url.push('#');
+                guide_url.push_str(heading_id);
+            }
+            Some(glossary::Tooltip {
+                term,
+                guide_url,
+                guide_title: guide.menu_title.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Writes `generated_guides/epub/seed-<version>.epub` for each Seed version.
+/// The modification time of EPUBs, the same in every build of the same sources:
+/// `SOURCE_DATE_EPOCH` if it's set, otherwise the date of the newest release.
+fn epub_modified(releases: &[changelog::Release]) -> String {
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    match env::var("SOURCE_DATE_EPOCH") {
+        Ok(seconds) => date::iso_8601(
+            seconds.parse().expect("SOURCE_DATE_EPOCH is a number of seconds"),
+        ),
+        Err(_) => releases
+            .iter()
+            .filter_map(|release| release.date.as_deref())
+            .max()
+            .map(changelog::timestamp)
+            .expect("the changelog has a dated release"),
+    }
+}
+
+fn write_epubs(
+    menu_guides: &[MenuGuide],
+    generated_guides: &HashMap<(String, String), (String, Vec<Heading>)>,
+    modified: &str,
+) {
+    if !Path::new(EPUB_FOLDER).exists() {
+        fs::create_dir(EPUB_FOLDER).unwrap();
+    }
+    let seed_versions = menu_guides
+        .iter()
+        .map(|guide| guide.seed_version.as_str())
+        .collect::<BTreeSet<_>>();
+
+    for seed_version in seed_versions {
+        let chapters = menu_guides
+            .iter()
+            .filter(|guide| guide.seed_version == seed_version)
+            .map(|guide| {
+                let (html, headings) = &generated_guides
+                    [&(guide.seed_version.clone(), guide.file_name.clone())];
+                epub::Chapter {
+                    file_name: &guide.file_name,
+                    title: &guide.menu_title,
+                    html,
+                    headings,
+                }
+            })
+            .collect::<Vec<_>>();
+        let epub_path = format!("{}/seed-{}.epub", EPUB_FOLDER, seed_version);
+        epub::write(seed_version, &chapters, modified, &epub_path);
+    }
+}
+
+/// Writes `generated_guides/<version>/<file_name>.png` for each guide.
+fn write_og_images(menu_guides: &[MenuGuide]) {
+    let renderer = og_image::Renderer::new();
+    for guide in menu_guides {
+        let path = format!(
+            "generated_guides/{}/{}.png",
+            guide.seed_version, guide.file_name
+        );
+        renderer.render(&guide.menu_title, &guide.seed_version, &path);
+    }
+}
+
+struct MenuGuide {
+    seed_version: String,
+    slug: String,
+    menu_title: String,
+    file_name: String,
+}
+
+/// Guides in the menu order, parsed from `guide!` calls in `src/guide.rs`.
+fn menu_guides() -> Vec<MenuGuide> {
+    let menu_guides = fs::read_to_string("src/guide.rs")
+        .unwrap()
+        .lines()
+        .filter_map(|line| {
+            let arguments = line.trim().strip_prefix("guide!(")?;
+            Some(MenuGuide {
+                seed_version: macro_argument(arguments, "seed_version")?,
+                slug: macro_argument(arguments, "slug")?,
+                menu_title: macro_argument(arguments, "menu_title")?,
+                file_name: macro_argument(arguments, "file_name")?,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Guides are found by the line format, so a reformatted `guides()`
+    // would silently leave versions without EPUBs, tooltips or images.
+    for entry in fs::read_dir("guides").unwrap() {
+        let seed_version = entry.unwrap().file_name();
+        let seed_version = seed_version.to_str().unwrap();
+        assert!(
+            menu_guides.iter().any(|guide| guide.seed_version == seed_version),
+            "No `guide!(` lines of Seed {} found in src/guide.rs",
+            seed_version
+        );
+    }
+    menu_guides
+}
+
+/// `macro_argument(r#"slug: "about", file_name: "about_seed""#, "file_name")` => `about_seed`
+fn macro_argument(arguments: &str, name: &str) -> Option<String> {
+    let prefix = format!(r#"{}: ""#, name);
+    let start = arguments.find(&prefix)? + prefix.len();
+    let len = arguments[start..].find('"')?;
+    Some(arguments[start..start + len].to_owned())
+}
+
+fn generated_guide_files() -> Vec<PathBuf> {
     fs::read_dir("generated_guides")
         .unwrap()
         .filter_map(|entry| {
@@ -50,7 +371,7 @@ fn html_and_text_files() -> Vec<PathBuf> {
             let path = entry.unwrap().path();
 
             match path.extension().unwrap_or_default().to_str().unwrap() {
-                "html" | "txt" => Some(path),
+                "html" | "txt" | "png" => Some(path),
                 _ => None,
             }
         })
@@ -80,21 +401,126 @@ fn markdown_files() -> Vec<PathBuf> {
         .collect()
 }
 
-fn markdown_to_html_and_text_parts(markdown: &str) -> (String, Vec<String>) {
+/// Lowercase words and code identifiers (e.g. `after_next_render`) for fuzzy search.
+fn vocabulary_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    const MIN_WORD_LEN: usize = 3;
+    const MAX_WORD_LEN: usize = 40;
+
+    text.split(|character: char| {
+        !character.is_alphanumeric() && character != '_'
+    })
+    .filter(|word| {
+        (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&word.chars().count())
+            && word.chars().any(char::is_alphabetic)
+    })
+    .map(str::to_lowercase)
+}
+
+struct Heading {
+    level: u32,
+    id: String,
+    title: String,
+}
+
+/// One heading per line: `level<TAB>id<TAB>title`.
+fn outline(headings: &[Heading]) -> String {
+    headings
+        .iter()
+        .map(|heading| {
+            format!("{}\t{}\t{}", heading.level, heading.id, heading.title)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Splits optional front matter from the markdown:
+/// ```text
+/// ---
+/// description: Guide description for search engines.
+/// ---
+/// ```
+fn split_front_matter(markdown: &str) -> (Option<&str>, &str) {
+    let end_delimiter = format!("\n{}", FRONT_MATTER_DELIMITER);
+    markdown
+        .strip_prefix(FRONT_MATTER_DELIMITER)
+        .and_then(|rest| {
+            let end = rest.find(&end_delimiter)?;
+            Some((Some(&rest[..end]), &rest[end + end_delimiter.len()..]))
+        })
+        .unwrap_or((None, markdown))
+}
+
+/// Values are on the same line as their keys and may be quoted.
+fn front_matter_value<'a>(front_matter: &'a str, key: &str) -> Option<&'a str> {
+    front_matter.lines().find_map(|line| {
+        let mut parts = line.splitn(2, ':');
+        if parts.next()?.trim() != key {
+            return None;
+        }
+        Some(parts.next()?.trim().trim_matches('"'))
+    })
+}
+
+/// Text of the first paragraph with text, e.g. the quote under the title.
+fn first_paragraph(markdown: &str) -> String {
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    for event in pulldown_cmark::Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(Tag::Paragraph) if !text.trim().is_empty() => break,
+            Event::End(Tag::Paragraph) => in_paragraph = false,
+            Event::Text(part) | Event::Code(part) if in_paragraph => {
+                text.push_str(&part);
+            },
+            Event::SoftBreak | Event::HardBreak if in_paragraph => {
+                text.push(' ');
+            },
+            _ => (),
+        }
+    }
+    text
+}
+
+/// Collapses whitespace and cuts the description at a word boundary.
+fn shorten_description(description: &str) -> String {
+    let description =
+        description.split_whitespace().collect::<Vec<_>>().join(" ");
+    if description.chars().count() <= DESCRIPTION_MAX_CHARS {
+        return description;
+    }
+    let mut shortened =
+        description.chars().take(DESCRIPTION_MAX_CHARS - 1).collect::<String>();
+    if let Some(last_space) = shortened.rfind(' ') {
+        shortened.truncate(last_space);
+    }
+    shortened.push('\u{2026}');
+    shortened
+}
+
+fn markdown_to_html_and_text_parts(
+    markdown: &str,
+    api_items: Option<&ApiItems>,
+    glossary_tooltips: Vec<glossary::Tooltip>,
+) -> (String, Vec<String>, Vec<Heading>) {
     let parser = pulldown_cmark::Parser::new(markdown);
 
     let mut html = String::new();
     let text_parts = Rc::new(RefCell::new(Vec::<String>::new()));
+    let mut headings = Vec::new();
 
-    let parser = extract_lowercase_text(parser, text_parts.clone());
-    let parser = transform_code_blocks(parser);
+    let parser = extract_text(parser, text_parts.clone());
+    let parser = link_api_items(parser, api_items);
+    let parser = glossary::wrap_terms(parser, glossary_tooltips);
+    let parser = add_heading_ids(parser, &mut headings);
+    let parser = transform_code_blocks(parser, api_items);
     let parser = add_details_el_key(parser);
 
     pulldown_cmark::html::push_html(&mut html, parser);
-    (html, text_parts.replace(Vec::new()))
+    (html, text_parts.replace(Vec::new()), headings)
 }
 
-fn extract_lowercase_text<'a, I>(
+fn extract_text<'a, I>(
     parser: I,
     text_parts: Rc<RefCell<Vec<String>>>,
 ) -> impl Iterator<Item = Event<'a>>
@@ -102,7 +528,7 @@ where
     I: Iterator<Item = Event<'a>>,
 {
     let push_to_text_parts = move |text: &str| {
-        text_parts.borrow_mut().push(text.to_lowercase());
+        text_parts.borrow_mut().push(text.to_owned());
     };
 
     parser.map(move |event| match event {
@@ -122,10 +548,148 @@ where
     })
 }
 
+/// Links inline code with Seed items to docs.rs, e.g. `ElRef` or `Orders::skip`.
+/// Headings and links are left untouched.
+fn link_api_items<'a, I>(
+    parser: I,
+    api_items: Option<&ApiItems>,
+) -> impl Iterator<Item = Event<'a>>
+where
+    I: Iterator<Item = Event<'a>>,
+{
+    let mut events = Vec::new();
+    let mut in_heading_or_link = false;
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::Heading(_)) | Event::Start(Tag::Link(..)) => {
+                in_heading_or_link = true;
+                events.push(event);
+            },
+            Event::End(Tag::Heading(_)) | Event::End(Tag::Link(..)) => {
+                in_heading_or_link = false;
+                events.push(event);
+            },
+            Event::Code(code) if !in_heading_or_link => {
+                match api_items.and_then(|api_items| api_items.url(&code)) {
+                    Some(url) => events.push(Event::Html(
+                        format!(
+                            r#"<a class="api-link" href="{}"><code>{}</code></a>"#,
+                            url,
+                            escape_html(&code)
+                        )
+                        .into(),
+                    )),
+                    None => events.push(Event::Code(code)),
+                }
+            },
+            _ => events.push(event),
+        }
+    }
+
+    events.into_iter()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Markdown rendered without the wrapping `<p>`.
+fn inline_html(markdown: &str) -> String {
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(
+        &mut html,
+        pulldown_cmark::Parser::new(markdown),
+    );
+    html.trim()
+        .trim_start_matches("<p>")
+        .trim_end_matches("</p>")
+        .replace('\n', " ")
+}
+
+/// Transforms headings to `<hX id="slug">` so we can link them and build outlines.
+#[allow(clippy::while_let_on_iterator)]
+fn add_heading_ids<'a, I>(
+    mut parser: I,
+    headings: &mut Vec<Heading>,
+) -> impl Iterator<Item = Event<'a>>
+where
+    I: Iterator<Item = Event<'a>>,
+{
+    let mut events = Vec::new();
+    let mut id_counts = HashMap::<String, usize>::new();
+
+    while let Some(event) = parser.next() {
+        match event {
+            Event::Start(Tag::Heading(level)) => {
+                let mut title = String::new();
+                let mut content = Vec::new();
+
+                while let Some(event) = parser.next() {
+                    match event {
+                        Event::End(Tag::Heading(_)) => break,
+                        Event::Text(ref text) | Event::Code(ref text) => {
+                            title.push_str(text);
+                            content.push(event);
+                        },
+                        _ => content.push(event),
+                    }
+                }
+
+                let slug = slugify(&title);
+                let count = id_counts.entry(slug.clone()).or_insert(0);
+                let id = if *count == 0 {
+                    slug
+                } else {
+                    format!("{}-{}", slug, count)
+                };
+                *count += 1;
+
+                events.push(Event::Html(
+                    format!("<h{} id=\"{}\">", level, id).into(),
+                ));
+                events.extend(content);
+                events.push(Event::Html(format!("</h{}>\n", level).into()));
+
+                headings.push(Heading {
+                    level,
+                    id,
+                    title: title.replace(char::is_whitespace, " "),
+                });
+            },
+            _ => events.push(event),
+        }
+    }
+
+    events.into_iter()
+}
+
+/// `Why Use Seed?` => `why-use-seed`, `Model - State` => `model-state`
+fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for character in title.to_lowercase().chars() {
+        match character {
+            _ if character.is_alphanumeric() || character == '_' => {
+                slug.push(character)
+            },
+            // Runs of separators are collapsed to one `-`.
+            ' ' | '-' if !slug.ends_with('-') => slug.push('-'),
+            _ => (),
+        }
+    }
+    if slug.is_empty() {
+        "section".to_owned()
+    } else {
+        slug
+    }
+}
+
 /// Transforms markdown code blocks to custom elements `<code-block lang="xx" code ="xx"></code-block>`.
+/// Seed items in the code are passed in `links` as `[[start, end, "url"]]`.
 #[allow(clippy::while_let_on_iterator)]
 fn transform_code_blocks<'a, I>(
     mut parser: I,
+    api_items: Option<&ApiItems>,
 ) -> impl Iterator<Item = Event<'a>>
 where
     I: Iterator<Item = Event<'a>>,
@@ -149,7 +713,14 @@ where
                             code.push_str(&text.to_string());
                         }
                         Event::End(Tag::CodeBlock(_)) => {
+                            let links = api_items
+                                .map(|api_items| api_items.code_links(&code))
+                                .unwrap_or_default();
                             attributes.push(("code", code));
+                            if !links.is_empty() {
+                                attributes
+                                    .push(("links", code_links_json(&links)));
+                            }
                             break;
                         }
                         _ => panic!("the closing code-block tag or text has to follow the opening code-block tag"),
@@ -174,6 +745,15 @@ where
     events.into_iter()
 }
 
+/// `[[4, 9, "https://docs.rs/seed/0.8.0/seed/macro.div.html"]]`
+fn code_links_json(links: &[(usize, usize, String)]) -> String {
+    let links = links
+        .iter()
+        .map(|(start, end, url)| format!(r#"[{},{},"{}"]"#, start, end, url))
+        .collect::<Vec<_>>();
+    format!("[{}]", links.join(","))
+}
+
 fn add_details_el_key<'a, I>(parser: I) -> impl Iterator<Item = Event<'a>>
 where
     I: Iterator<Item = Event<'a>>,
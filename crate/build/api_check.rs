@@ -0,0 +1,77 @@
+//! Checks that Seed items referenced in guides exist in the guide's Seed version.
+//! A reference is stale if it isn't in the version's item list,
+//! but it's in the list of another version - e.g. a method renamed in 0.8.0
+//! and still mentioned in 0.8.0 guides.
+//! Other unknown references are app items like `Model` or `Msg::Increment`.
+
+use crate::api_items::{self, ApiItems};
+use pulldown_cmark::{Event, Tag};
+
+pub struct StaleReference {
+    pub line: usize,
+    pub reference: String,
+    /// Seed versions with the item.
+    pub seed_versions: Vec<String>,
+}
+
+/// `first_line` is the markdown's line number in its file (after front matter).
+pub fn stale_references(
+    markdown: &str,
+    first_line: usize,
+    api_items: &ApiItems,
+    other_api_items: &[&ApiItems],
+) -> Vec<StaleReference> {
+    let line =
+        |offset: usize| first_line + markdown[..offset].matches('\n').count();
+    let mut references = Vec::new();
+    let mut in_code_block = false;
+    for (event, range) in
+        pulldown_cmark::Parser::new(markdown).into_offset_iter()
+    {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Code(code) => {
+                if let Some(reference) = api_items::normalize(&code) {
+                    references.push((line(range.start), reference));
+                }
+            },
+            Event::Text(code) if in_code_block => {
+                for (index, code_line) in code.lines().enumerate() {
+                    // Comments like `// ------ Init ------` aren't references.
+                    let code_line =
+                        code_line.split("//").next().unwrap_or_default();
+                    for (_, reference) in api_items::code_references(code_line)
+                    {
+                        references.push((
+                            line(range.start) + index,
+                            reference.to_owned(),
+                        ));
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+
+    references
+        .into_iter()
+        .filter(|(_, reference)| !api_items.contains(reference))
+        .filter_map(|(line, reference)| {
+            let seed_versions = other_api_items
+                .iter()
+                .filter(|other| other.contains(&reference))
+                .map(|other| other.seed_version().to_owned())
+                .collect::<Vec<_>>();
+            if seed_versions.is_empty() {
+                None
+            } else {
+                Some(StaleReference {
+                    line,
+                    reference,
+                    seed_versions,
+                })
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,214 @@
+//! Public Seed items listed in `api_items/<version>.txt`
+//! (see `scripts/api_items.js`) for linking guides to docs.rs.
+//!
+//! References are matched by path suffixes: `ElRef`, `Orders::skip`,
+//! `app::orders::Orders::skip` and `seed::struct_urls!` are all found.
+//! Lowercase items (functions, methods), enum variants, type aliases
+//! and generic names like `Status` need their parent in the reference,
+//! so local variables and the guides' own types aren't linked.
+
+use std::{collections::HashMap, fs, path::Path};
+
+const DOCS_URL: &str = "https://docs.rs/seed";
+const CRATE_PREFIX: &str = "seed::";
+/// Seed's type aliases like `fetch::Result` are linked only with their module.
+const STD_PRELUDE_NAMES: &[&str] =
+    &["Box", "Option", "Result", "String", "Vec", "Some", "None", "Ok", "Err"];
+/// Names that apps in guides use for their own types,
+/// e.g. `fetch::Status` is linked but `Status` isn't.
+const GENERIC_NAMES: &[&str] = &[
+    "Builder", "Header", "Headers", "Method", "State", "Status", "Unit",
+];
+
+pub struct ApiItems {
+    seed_version: String,
+    /// Reference => (path length, docs.rs page).
+    /// The page is `None` if more items with the shortest path match.
+    pages: HashMap<String, (usize, Option<String>)>,
+}
+
+impl ApiItems {
+    /// Returns `None` if there is no list for the Seed version.
+    pub fn load(seed_version: &str) -> Option<Self> {
+        let path = format!("api_items/{}.txt", seed_version);
+        if !Path::new(&path).exists() {
+            return None;
+        }
+        let mut items = Self {
+            seed_version: seed_version.to_owned(),
+            pages: HashMap::new(),
+        };
+        for line in fs::read_to_string(path).unwrap().lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '\t');
+            if let (Some(path), Some(page)) = (parts.next(), parts.next()) {
+                items.insert(path, page);
+            }
+        }
+        Some(items)
+    }
+
+    pub fn seed_version(&self) -> &str {
+        &self.seed_version
+    }
+
+    fn insert(&mut self, path: &str, page: &str) {
+        let segments = path.split("::").collect::<Vec<_>>();
+        let name = segments[segments.len() - 1];
+        if page.contains("/macro.") {
+            self.insert_reference(format!("{}!", name), 1, page);
+            return;
+        }
+        // Enum variants and methods have `#` in their pages.
+        let is_type = !page.contains('#')
+            && !page.contains("/type.")
+            && name.starts_with(|character: char| character.is_uppercase());
+        let min_len = if is_type
+            && !STD_PRELUDE_NAMES.contains(&name)
+            && !GENERIC_NAMES.contains(&name)
+        {
+            1
+        } else {
+            2
+        };
+        for len in min_len..=segments.len() {
+            let reference = segments[segments.len() - len..].join("::");
+            self.insert_reference(reference, segments.len(), page);
+        }
+    }
+
+    fn insert_reference(
+        &mut self,
+        reference: String,
+        path_len: usize,
+        page: &str,
+    ) {
+        let entry = self
+            .pages
+            .entry(reference)
+            .or_insert_with(|| (path_len, Some(page.to_owned())));
+        if path_len < entry.0 {
+            *entry = (path_len, Some(page.to_owned()));
+        } else if path_len == entry.0 && entry.1.as_deref() != Some(page) {
+            entry.1 = None;
+        }
+    }
+
+    /// docs.rs URL of the referenced item,
+    /// e.g. `Orders::after_next_render()` or `ElRef<HtmlCanvasElement>`.
+    pub fn url(&self, reference: &str) -> Option<String> {
+        let page = self.pages.get(&normalize(reference)?)?.1.as_ref()?;
+        Some(format!("{}/{}/{}", DOCS_URL, self.seed_version, page))
+    }
+
+    /// Whether the reference matches any item, even an ambiguous one.
+    /// `reference` has to be normalized.
+    pub fn contains(&self, reference: &str) -> bool {
+        self.pages.contains_key(reference)
+    }
+
+    /// Ranges of linked references in the code as `(start, end, url)`.
+    /// Offsets are in UTF-16 code units to be used in JavaScript.
+    pub fn code_links(&self, code: &str) -> Vec<(usize, usize, String)> {
+        let mut links = Vec::new();
+        for (start, reference) in code_references(code) {
+            // `Url::new().add_path_part` => `Url::new` or `Url`
+            let linked = (1..=reference.matches("::").count() + 1)
+                .rev()
+                .find_map(|segment_count| {
+                    let end = reference
+                        .match_indices("::")
+                        .nth(segment_count - 1)
+                        .map_or(reference.len(), |(index, _)| index);
+                    let reference = &reference[..end];
+                    Some((reference, self.url(reference)?))
+                });
+            if let Some((reference, url)) = linked {
+                let start_utf16 = code[..start].encode_utf16().count();
+                links.push((
+                    start_utf16,
+                    start_utf16 + reference.encode_utf16().count(),
+                    url,
+                ));
+            }
+        }
+        links
+    }
+}
+
+/// `&mut seed::Orders::skip()` => `Orders::skip`
+pub fn normalize(reference: &str) -> Option<String> {
+    let reference = reference
+        .trim()
+        .trim_start_matches('&')
+        .trim_start_matches("mut ")
+        .trim_start();
+    let end = reference
+        .find(&['(', '<', '[', '{', ' '][..])
+        .unwrap_or(reference.len());
+    let reference = &reference[..end];
+    let reference = reference.strip_prefix(CRATE_PREFIX).unwrap_or(reference);
+    let is_path = reference.trim_end_matches('!').split("::").all(|segment| {
+        !segment.is_empty()
+            && segment.chars().all(|character| {
+                character.is_alphanumeric() || character == '_'
+            })
+    });
+    if is_path {
+        Some(reference.to_owned())
+    } else {
+        None
+    }
+}
+
+/// Paths like `seed::app::Orders` or `struct_urls!` with their byte offsets.
+/// Paths after `.` (fields and method calls) are skipped.
+pub fn code_references(code: &str) -> Vec<(usize, &str)> {
+    let is_identifier_char =
+        |character: char| character.is_alphanumeric() || character == '_';
+    let mut references = Vec::new();
+    let mut index = 0;
+    while index < code.len() {
+        let rest = &code[index..];
+        let character = rest.chars().next().unwrap();
+        let previous = code[..index].chars().next_back();
+        if !(character.is_alphabetic() || character == '_')
+            || previous.map_or(false, |previous| {
+                is_identifier_char(previous) || previous == '.'
+            })
+        {
+            index += character.len_utf8();
+            continue;
+        }
+        let mut len = 0;
+        loop {
+            len += rest[len..]
+                .find(|character| !is_identifier_char(character))
+                .unwrap_or(rest.len() - len);
+            let after = &rest[len..];
+            if after.starts_with("::")
+                && after[2..].starts_with(|character: char| {
+                    character.is_alphabetic() || character == '_'
+                })
+            {
+                len += 2;
+            } else {
+                if after.starts_with('!') {
+                    len += 1;
+                }
+                break;
+            }
+        }
+        let reference = &rest[..len];
+        let reference_start = if reference.starts_with(CRATE_PREFIX) {
+            index + CRATE_PREFIX.len()
+        } else {
+            index
+        };
+        references.push((reference_start, &code[reference_start..index + len]));
+        index += len;
+    }
+    references
+}
@@ -0,0 +1,209 @@
+//! Releases parsed from the Seed changelog for the changelog page
+//! and its Atom feed.
+//!
+//! ```text
+//! ## v0.6.0 - 2020-02-01
+//! - [BREAKING] Removed deprecated `update` and `trigger_update_ev`.
+//! - Fixed custom elements patching (#325).
+//! ```
+//!
+//! Entries may be marked with `[BREAKING]`, `[feature]` or `[fix]`
+//! (old entries end with `(breaking)` instead), unmarked entries get
+//! their kind from the first word (`Added`, `Fixed`).
+//! The unreleased section before the first release is skipped.
+
+use crate::{inline_html, SITE_URL};
+
+const RELEASE_PREFIX: &str = "## ";
+const ENTRY_PREFIX: &str = "- ";
+const DATE_SEPARATOR: &str = " - ";
+
+pub struct Release {
+    pub version: String,
+    /// `2020-02-01`
+    pub date: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+pub struct Entry {
+    /// `breaking`, `feature`, `fix` or `other`.
+    pub kind: &'static str,
+    /// Inline HTML without the marker.
+    pub html: String,
+}
+
+pub fn parse(markdown: &str) -> Vec<Release> {
+    let mut releases = Vec::<Release>::new();
+    let mut entry_markdowns = Vec::<String>::new();
+    for line in markdown.lines() {
+        if let Some(heading) = line.strip_prefix(RELEASE_PREFIX) {
+            if let Some(release) = releases.last_mut() {
+                release.entries = entry_markdowns
+                    .drain(..)
+                    .map(|text| entry(&text))
+                    .collect();
+            }
+            let mut parts = heading.splitn(2, DATE_SEPARATOR);
+            releases.push(Release {
+                version: parts
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .trim_start_matches(|character| {
+                        character == 'v' || character == 'V'
+                    })
+                    .to_owned(),
+                date: parts.next().map(|date| date.trim().to_owned()),
+                entries: Vec::new(),
+            });
+        } else if releases.is_empty() {
+            continue;
+        } else if let Some(text) = line.strip_prefix(ENTRY_PREFIX) {
+            entry_markdowns.push(text.trim().to_owned());
+        } else if let Some(text) = entry_markdowns.last_mut() {
+            if !line.trim().is_empty() {
+                text.push(' ');
+                text.push_str(line.trim());
+            }
+        }
+    }
+    if let Some(release) = releases.last_mut() {
+        release.entries =
+            entry_markdowns.drain(..).map(|text| entry(&text)).collect();
+    }
+    releases
+}
+
+fn entry(markdown: &str) -> Entry {
+    let (marker, text) = match markdown.strip_prefix('[') {
+        Some(rest) => {
+            let end = rest.find(']').unwrap_or(rest.len());
+            let text = rest[end..].trim_start_matches(']').trim_start();
+            (
+                Some(rest[..end].to_lowercase()),
+                text.trim_start_matches(ENTRY_PREFIX),
+            )
+        },
+        None => (None, markdown),
+    };
+    let kind = match marker.as_deref() {
+        Some("breaking") => "breaking",
+        Some("feature") => "feature",
+        Some("fix") => "fix",
+        Some(_) => "other",
+        None if text.to_lowercase().ends_with("(breaking)") => "breaking",
+        None => match text.split_whitespace().next().unwrap_or_default() {
+            "Added" | "Add" | "Implemented" => "feature",
+            "Fixed" | "Fix" => "fix",
+            _ => "other",
+        },
+    };
+    Entry {
+        kind,
+        html: inline_html(text),
+    }
+}
+
+/// One entry per line: `version<TAB>date<TAB>kind<TAB>html`.
+/// Releases without entries are omitted.
+pub fn tsv(releases: &[Release]) -> String {
+    releases
+        .iter()
+        .flat_map(|release| {
+            release.entries.iter().map(move |entry| {
+                format!(
+                    "{}\t{}\t{}\t{}",
+                    release.version,
+                    release.date.as_deref().unwrap_or_default(),
+                    entry.kind,
+                    entry.html
+                )
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Atom requires an update time, so undated releases are skipped.
+pub fn atom_feed(releases: &[Release]) -> String {
+    let releases = releases
+        .iter()
+        .filter(|release| {
+            if release.date.is_none() {
+                println!(
+                    "cargo:warning=Release {} has no date in the changelog heading, it's not in the Atom feed",
+                    release.version
+                );
+            }
+            release.date.is_some()
+        })
+        .collect::<Vec<_>>();
+    let updated = releases
+        .iter()
+        .filter_map(|release| release.date.as_deref())
+        .max()
+        .map_or_else(String::new, timestamp);
+    let entries = releases.into_iter().map(atom_entry).collect::<String>();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>{site_url}/changelog</id>
+  <title>Seed releases</title>
+  <link rel="self" href="{site_url}/changelog.atom"/>
+  <link rel="alternate" type="text/html" href="{site_url}/changelog"/>
+  <author><name>Seed contributors</name></author>
+  <updated>{updated}</updated>
+{entries}</feed>
+"#,
+        site_url = SITE_URL,
+        updated = updated,
+        entries = entries,
+    )
+}
+
+fn atom_entry(release: &Release) -> String {
+    let url = format!(
+        "{}/changelog?from={version}&to={version}",
+        SITE_URL,
+        version = release.version
+    );
+    let items = release
+        .entries
+        .iter()
+        .map(|entry| {
+            if entry.kind == "breaking" {
+                format!("<li><strong>Breaking:</strong> {}</li>", entry.html)
+            } else {
+                format!("<li>{}</li>", entry.html)
+            }
+        })
+        .collect::<String>();
+
+    format!(
+        r#"  <entry>
+    <id>{url}</id>
+    <title>Seed {version}</title>
+    <link rel="alternate" type="text/html" href="{url}"/>
+    <updated>{updated}</updated>
+    <content type="html">{content}</content>
+  </entry>
+"#,
+        url = escape(&url),
+        version = release.version,
+        updated = timestamp(release.date.as_deref().unwrap_or_default()),
+        content = escape(&format!("<ul>{}</ul>", items)),
+    )
+}
+
+/// `2020-02-01` => `2020-02-01T00:00:00Z`
+pub fn timestamp(date: &str) -> String {
+    format!("{}T00:00:00Z", date)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
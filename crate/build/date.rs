@@ -0,0 +1,54 @@
+//! Dates for build outputs without a date library.
+
+/// Seconds since the Unix epoch => `2020-10-23T08:30:00Z`
+pub fn iso_8601(seconds: u64) -> String {
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era
+        + era * 400
+        + if month <= 2 {
+            1
+        } else {
+            0
+        };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso_8601_formats_seconds_since_unix_epoch() {
+        assert_eq!(iso_8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(iso_8601(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(iso_8601(1_580_515_200), "2020-02-01T00:00:00Z");
+        assert_eq!(iso_8601(1_608_122_096), "2020-12-16T12:34:56Z");
+        assert_eq!(iso_8601(4_102_444_799), "2099-12-31T23:59:59Z");
+    }
+}
@@ -0,0 +1,297 @@
+//! EPUB 3 export of the guides of one Seed version.
+//! Chapters are the guide HTML files converted to XHTML
+//! with highlighted code blocks and bundled images.
+
+use crate::{code_block, highlight, Heading, SITE_URL};
+use std::{fs, io::Write};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+const IMAGE_FOLDER: &str = "../static/images";
+const IMAGE_SRC_PREFIX: &str = r#" src="/static/images/"#;
+const STYLESHEET: &str = include_str!("epub.css");
+
+pub struct Chapter<'a> {
+    pub file_name: &'a str,
+    pub title: &'a str,
+    pub html: &'a str,
+    pub headings: &'a [Heading],
+}
+
+/// `modified` is the EPUB modification time, e.g. `2020-10-23T08:30:00Z`.
+pub fn write(
+    seed_version: &str,
+    chapters: &[Chapter],
+    modified: &str,
+    path: &str,
+) {
+    let mut images = Vec::new();
+    let chapter_files = chapters
+        .iter()
+        .map(|chapter| {
+            let xhtml = chapter_xhtml(chapter, &mut images);
+            (format!("chapters/{}.xhtml", chapter.file_name), xhtml)
+        })
+        .collect::<Vec<_>>();
+
+    let mut zip = ZipWriter::new(fs::File::create(path).unwrap());
+    // `mimetype` has to be the first file and it mustn't be compressed.
+    let stored =
+        FileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = FileOptions::default();
+    let mut add_file = |name: &str, content: &[u8], options| {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(content).unwrap();
+    };
+
+    add_file("mimetype", b"application/epub+zip", stored);
+    add_file("META-INF/container.xml", CONTAINER_XML.as_bytes(), deflated);
+    add_file(
+        "OEBPS/content.opf",
+        package_document(seed_version, chapters, &images, modified).as_bytes(),
+        deflated,
+    );
+    add_file(
+        "OEBPS/nav.xhtml",
+        navigation_document(seed_version, chapters).as_bytes(),
+        deflated,
+    );
+    add_file("OEBPS/style.css", STYLESHEET.as_bytes(), deflated);
+    for (name, xhtml) in chapter_files {
+        add_file(&format!("OEBPS/{}", name), xhtml.as_bytes(), deflated);
+    }
+    for image in &images {
+        let content = fs::read(format!("{}/{}", IMAGE_FOLDER, image)).unwrap();
+        // Images are already compressed.
+        add_file(&format!("OEBPS/images/{}", image), &content, stored);
+    }
+    zip.finish().unwrap();
+}
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+fn package_document(
+    seed_version: &str,
+    chapters: &[Chapter],
+    images: &[String],
+    modified: &str,
+) -> String {
+    let chapter_items = chapters.iter().map(|chapter| {
+        format!(
+            r#"<item id="chapter-{0}" href="chapters/{0}.xhtml" media-type="application/xhtml+xml"/>"#,
+            chapter.file_name
+        )
+    });
+    let image_items = images.iter().enumerate().map(|(index, image)| {
+        format!(
+            r#"<item id="image-{}" href="images/{}" media-type="{}"/>"#,
+            index,
+            image,
+            image_media_type(image)
+        )
+    });
+    let items = chapter_items.chain(image_items).collect::<Vec<_>>();
+    let itemrefs = chapters
+        .iter()
+        .map(|chapter| {
+            format!(r#"<itemref idref="chapter-{}"/>"#, chapter.file_name)
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id" xml:lang="en">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="book-id">{site_url}/guide/{seed_version}</dc:identifier>
+    <dc:title>Seed {seed_version} guides</dc:title>
+    <dc:creator>Seed contributors</dc:creator>
+    <dc:language>en</dc:language>
+    <meta property="dcterms:modified">{modified}</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="style" href="style.css" media-type="text/css"/>
+    {items}
+  </manifest>
+  <spine>
+    {itemrefs}
+  </spine>
+</package>
+"#,
+        site_url = SITE_URL,
+        seed_version = seed_version,
+        modified = modified,
+        items = items.join("\n    "),
+        itemrefs = itemrefs.join("\n    "),
+    )
+}
+
+/// The table of contents with guides and their level 2 and 3 headings.
+fn navigation_document(seed_version: &str, chapters: &[Chapter]) -> String {
+    let mut toc = String::new();
+    for chapter in chapters {
+        let href = format!("chapters/{}.xhtml", chapter.file_name);
+        toc.push_str(&format!(
+            r#"<li><a href="{}">{}</a>"#,
+            href,
+            escape(chapter.title)
+        ));
+        let mut open_levels = Vec::<u32>::new();
+        for heading in chapter
+            .headings
+            .iter()
+            .filter(|heading| (2..=3).contains(&heading.level))
+        {
+            while open_levels
+                .last()
+                .map_or(false, |level| *level > heading.level)
+            {
+                toc.push_str("</li></ol>");
+                open_levels.pop();
+            }
+            if open_levels.last() == Some(&heading.level) {
+                toc.push_str("</li>");
+            } else {
+                toc.push_str("<ol>");
+                open_levels.push(heading.level);
+            }
+            toc.push_str(&format!(
+                r##"<li><a href="{}#{}">{}</a>"##,
+                href,
+                heading.id,
+                escape(&heading.title)
+            ));
+        }
+        for _ in open_levels {
+            toc.push_str("</li></ol>");
+        }
+        toc.push_str("</li>\n");
+    }
+
+    xhtml_document(
+        &format!("Seed {} guides", seed_version),
+        "",
+        &format!(
+            r#"<nav epub:type="toc" id="toc"><h1>Contents</h1><ol>
+{}</ol></nav>"#,
+            toc
+        ),
+    )
+}
+
+fn chapter_xhtml(chapter: &Chapter, images: &mut Vec<String>) -> String {
+    let html = remove_glossary_tooltips(&highlight_code_blocks(chapter.html))
+        .replace("&nbsp;", "&#160;")
+        .replace(r#" href="/"#, &format!(r#" href="{}/"#, SITE_URL));
+    let html = bundle_images(&html, images);
+    xhtml_document(
+        chapter.title,
+        "../",
+        &format!(r#"<section epub:type="chapter">{}</section>"#, html),
+    )
+}
+
+fn xhtml_document(title: &str, root: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" lang="en" xml:lang="en">
+<head>
+<meta charset="UTF-8"/>
+<title>{}</title>
+<link rel="stylesheet" type="text/css" href="{}style.css"/>
+</head>
+<body>
+{}
+</body>
+</html>
+"#,
+        escape(title),
+        root,
+        body
+    )
+}
+
+/// Replaces `<code-block lang="xx" code="xx"></code-block>` elements
+/// generated by `build.rs` with highlighted `<pre><code>` blocks.
+fn highlight_code_blocks(html: &str) -> String {
+    const END: &str = "></code-block>";
+
+    let mut xhtml = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(code_block::START) {
+        let end = start + rest[start..].find(END).unwrap();
+        let tag = &rest[start..end];
+        let lang = code_block::attribute(tag, "lang").unwrap_or_default();
+        let code = code_block::attribute(tag, "code").unwrap_or_default();
+
+        xhtml.push_str(&rest[..start]);
+        xhtml.push_str(&format!(
+            r#"<pre><code class="hljs language-{}">{}</code></pre>"#,
+            lang,
+            highlight::highlight(&code, &lang)
+        ));
+        rest = &rest[end + END.len()..];
+    }
+    xhtml.push_str(rest);
+    xhtml
+}
+
+/// E-readers can't show tooltips of glossary terms (see `build/glossary.rs`),
+/// they would be rendered inline.
+fn remove_glossary_tooltips(html: &str) -> String {
+    const START: &str = r#"<span class="glossary-tooltip""#;
+    const END: &str = "</span>";
+
+    let mut xhtml = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(START) {
+        let end = start + rest[start..].find(END).unwrap();
+        xhtml.push_str(&rest[..start]);
+        rest = &rest[end + END.len()..];
+    }
+    xhtml.push_str(rest);
+    xhtml
+}
+
+/// Points images from `/static/images/` to the bundled copies
+/// and adds their file names to `images`.
+fn bundle_images(html: &str, images: &mut Vec<String>) -> String {
+    let mut xhtml = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(IMAGE_SRC_PREFIX) {
+        let name_start = start + IMAGE_SRC_PREFIX.len();
+        let name_len = rest[name_start..].find('"').unwrap();
+        let name = &rest[name_start..name_start + name_len];
+        if !images.iter().any(|image| image == name) {
+            images.push(name.to_owned());
+        }
+        xhtml.push_str(&rest[..start]);
+        xhtml.push_str(r#" src="../images/"#);
+        rest = &rest[name_start..];
+    }
+    xhtml.push_str(rest);
+    xhtml
+}
+
+fn image_media_type(file_name: &str) -> &'static str {
+    match file_name.rsplit('.').next().unwrap_or_default() {
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "jpg" | "jpeg" => "image/jpeg",
+        "svg" => "image/svg+xml",
+        extension => panic!("unsupported image type '{}'", extension),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,254 @@
+//! Seed terms parsed from `glossary.md` for the glossary page
+//! and for tooltips in guides.
+//!
+//! ```text
+//! ## `ElRef`
+//! guide: todomvc_update
+//! forms: ElRef
+//!
+//! A typed reference to a DOM element, ...
+//! ```
+//!
+//! The first occurrence of each term in a guide is wrapped with a tooltip
+//! with the definition and a link to the guide that explains the term.
+//! Headings, links, images and code blocks are left untouched.
+
+use crate::{escape_html, inline_html, slugify};
+use pulldown_cmark::{Event, Tag};
+use std::cmp::Reverse;
+
+const TERM_PREFIX: &str = "## ";
+const GUIDE_KEY: &str = "guide:";
+const FORMS_KEY: &str = "forms:";
+
+pub struct Term {
+    /// Anchor on the glossary page, e.g. `struct_urls`.
+    pub id: String,
+    pub html: String,
+    /// Words wrapped in guides, e.g. `subscription` and `subscriptions`.
+    pub forms: Vec<String>,
+    /// File name of the guide that explains the term.
+    pub guide: String,
+    pub heading_id: Option<String>,
+    pub definition_html: String,
+}
+
+/// Text before the first term (e.g. a comment with the format) is skipped.
+pub fn parse(markdown: &str) -> Vec<Term> {
+    format!("\n{}", markdown)
+        .split(&format!("\n{}", TERM_PREFIX))
+        .skip(1)
+        .map(term)
+        .collect()
+}
+
+fn term(section: &str) -> Term {
+    let mut lines = section.lines();
+    let name = lines.next().unwrap_or_default().trim();
+    let mut guide = None;
+    let mut forms = None;
+    let mut definition = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if let Some(value) = line.strip_prefix(GUIDE_KEY) {
+            guide = Some(value.trim());
+        } else if let Some(value) = line.strip_prefix(FORMS_KEY) {
+            forms = Some(value);
+        } else if !line.is_empty() {
+            definition.push(line);
+        }
+    }
+    let plain_name = name.trim_matches('`');
+    let mut guide_parts = guide
+        .unwrap_or_else(|| panic!("Glossary term `{}` has no guide", name))
+        .splitn(2, '#');
+
+    Term {
+        id: slugify(plain_name),
+        html: inline_html(name),
+        forms: forms.map_or_else(
+            || vec![plain_name.to_owned()],
+            |forms| {
+                forms
+                    .split(',')
+                    .map(|form| form.trim().to_owned())
+                    .filter(|form| !form.is_empty())
+                    .collect()
+            },
+        ),
+        guide: guide_parts.next().unwrap_or_default().to_owned(),
+        heading_id: guide_parts.next().map(ToOwned::to_owned),
+        definition_html: inline_html(&definition.join(" ")),
+    }
+}
+
+/// One term per line: `id<TAB>html<TAB>guide slug<TAB>heading id<TAB>definition html`.
+pub fn tsv(terms: &[Term], guide_slug: impl Fn(&Term) -> String) -> String {
+    terms
+        .iter()
+        .map(|term| {
+            format!(
+                "{}\t{}\t{}\t{}\t{}",
+                term.id,
+                term.html,
+                guide_slug(term),
+                term.heading_id.as_deref().unwrap_or_default(),
+                term.definition_html
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ------ Tooltip ------
+
+/// The term with its guide in the Seed version of the wrapped guide.
+pub struct Tooltip<'a> {
+    pub term: &'a Term,
+    /// `/0.8.0/init#parameter-orders-mut-impl-ordersmsg`
+    pub guide_url: String,
+    pub guide_title: String,
+}
+
+impl Tooltip<'_> {
+    fn html(&self, content: &str) -> String {
+        format!(
+            r#"<span class="glossary-term" tabindex="0">{}<span class="glossary-tooltip" role="tooltip">{} Explained in <a href="{}">{}</a>.</span></span>"#,
+            content,
+            self.term.definition_html,
+            self.guide_url,
+            escape_html(&self.guide_title)
+        )
+    }
+}
+
+/// Wraps the first occurrence of each term with its tooltip.
+pub fn wrap_terms<'a, I>(
+    parser: I,
+    mut tooltips: Vec<Tooltip>,
+) -> impl Iterator<Item = Event<'a>>
+where
+    I: Iterator<Item = Event<'a>>,
+{
+    let mut events = Vec::new();
+    let mut ignored_depth = 0;
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::Heading(_))
+            | Event::Start(Tag::Link(..))
+            | Event::Start(Tag::Image(..))
+            | Event::Start(Tag::CodeBlock(_)) => {
+                ignored_depth += 1;
+                events.push(event);
+            },
+            Event::End(Tag::Heading(_))
+            | Event::End(Tag::Link(..))
+            | Event::End(Tag::Image(..))
+            | Event::End(Tag::CodeBlock(_)) => {
+                ignored_depth -= 1;
+                events.push(event);
+            },
+            Event::Text(text) if ignored_depth == 0 => {
+                let mut rest = &*text;
+                while let Some((start, len, index)) =
+                    first_term(rest, &tooltips)
+                {
+                    let tooltip = tooltips.remove(index);
+                    let term = &rest[start..start + len];
+                    events.push(Event::Text(rest[..start].to_owned().into()));
+                    events.push(Event::Html(
+                        tooltip.html(&escape_html(term)).into(),
+                    ));
+                    rest = &rest[start + len..];
+                }
+                events.push(Event::Text(rest.to_owned().into()));
+            },
+            Event::Code(code) if ignored_depth == 0 => {
+                match code_term(&code, &tooltips) {
+                    Some(index) => {
+                        let code =
+                            format!("<code>{}</code>", escape_html(&code));
+                        events.push(Event::Html(
+                            tooltips.remove(index).html(&code).into(),
+                        ));
+                    },
+                    None => events.push(Event::Code(code)),
+                }
+            },
+            // Inline code linked by `link_api_items` keeps its link.
+            Event::Html(html) if ignored_depth == 0 => {
+                match api_link_code(&html)
+                    .and_then(|code| code_term(code, &tooltips))
+                {
+                    Some(index) => events.push(Event::Html(
+                        tooltips.remove(index).html(&html).into(),
+                    )),
+                    None => events.push(Event::Html(html)),
+                }
+            },
+            _ => events.push(event),
+        }
+    }
+
+    events.into_iter()
+}
+
+/// `(start, length, tooltip index)` of the first term in the text.
+/// The longest form wins if more forms start at the same position.
+fn first_term(
+    text: &str,
+    tooltips: &[Tooltip],
+) -> Option<(usize, usize, usize)> {
+    tooltips
+        .iter()
+        .enumerate()
+        .flat_map(|(index, tooltip)| {
+            tooltip.term.forms.iter().filter_map(move |form| {
+                word_start(text, form).map(|start| (start, form.len(), index))
+            })
+        })
+        .min_by_key(|(start, len, _)| (*start, Reverse(*len)))
+}
+
+/// The first occurrence of the word that isn't a part of another word.
+fn word_start(text: &str, word: &str) -> Option<usize> {
+    text.match_indices(word).map(|(start, _)| start).find(|start| {
+        let before = text[..*start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.map_or(false, is_identifier_char)
+            && !after.map_or(false, is_identifier_char)
+    })
+}
+
+/// Index of the tooltip whose term starts the inline code.
+fn code_term(code: &str, tooltips: &[Tooltip]) -> Option<usize> {
+    tooltips.iter().position(|tooltip| {
+        tooltip
+            .term
+            .forms
+            .iter()
+            .any(|form| code_starts_with_term(code, form))
+    })
+}
+
+/// Escaped code of `<a class="api-link" href="…"><code>…</code></a>`.
+fn api_link_code(html: &str) -> Option<&str> {
+    let rest = html.strip_prefix(r#"<a class="api-link" href=""#)?;
+    let (_, rest) = rest.split_at(rest.find(r#""><code>"#)?);
+    rest.strip_prefix(r#""><code>"#)?.strip_suffix("</code></a>")
+}
+
+/// `ElRef<HtmlInputElement>` starts with `ElRef`,
+/// `Msg::Increment` doesn't start with `Msg`.
+fn code_starts_with_term(code: &str, form: &str) -> bool {
+    code.strip_prefix(form).map_or(false, |rest| {
+        !rest.starts_with(|character| {
+            is_identifier_char(character) || character == ':'
+        })
+    })
+}
+
+fn is_identifier_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
@@ -0,0 +1,345 @@
+//! A small lexical highlighter for code blocks in EPUBs,
+//! where `highlight.js` can't run.
+//! It emits `highlight.js` class names, so the same color scheme applies.
+
+struct Syntax {
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    keywords: &'static [&'static str],
+    literals: &'static [&'static str],
+    single_quote_strings: bool,
+    /// Rust macros (`vec!`), attributes (`#[derive]`) and types (`Model`).
+    rust_items: bool,
+    /// HTML tag names (`<div>`).
+    tags: bool,
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn",
+    "else", "enum", "extern", "fn", "for", "if", "impl", "in", "let", "loop",
+    "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "type", "unsafe", "use", "where",
+    "while",
+];
+
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "of",
+    "return",
+    "static",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "type",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+const GRAPHQL_KEYWORDS: &[&str] = &[
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+    "on",
+    "type",
+    "input",
+    "enum",
+    "interface",
+    "union",
+    "scalar",
+    "schema",
+    "extend",
+    "implements",
+];
+
+const BASH_KEYWORDS: &[&str] = &[
+    "if", "then", "else", "fi", "for", "do", "done", "while", "case", "esac",
+    "export", "cd", "echo",
+];
+
+fn syntax(lang: &str) -> Option<Syntax> {
+    let plain = Syntax {
+        line_comment: None,
+        block_comment: None,
+        keywords: &[],
+        literals: &[],
+        single_quote_strings: false,
+        rust_items: false,
+        tags: false,
+    };
+    Some(match lang {
+        "rust" | "rs" => Syntax {
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            keywords: RUST_KEYWORDS,
+            literals: &["true", "false", "None", "Some", "Ok", "Err"],
+            rust_items: true,
+            ..plain
+        },
+        "js" | "ts" | "tsx" => Syntax {
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            keywords: JS_KEYWORDS,
+            literals: &["true", "false", "null", "undefined"],
+            single_quote_strings: true,
+            ..plain
+        },
+        "toml" => Syntax {
+            line_comment: Some("#"),
+            literals: &["true", "false"],
+            single_quote_strings: true,
+            ..plain
+        },
+        "bash" | "sh" => Syntax {
+            line_comment: Some("#"),
+            keywords: BASH_KEYWORDS,
+            single_quote_strings: true,
+            ..plain
+        },
+        "graphql" => Syntax {
+            line_comment: Some("#"),
+            keywords: GRAPHQL_KEYWORDS,
+            literals: &["true", "false", "null"],
+            ..plain
+        },
+        "json" => Syntax {
+            literals: &["true", "false", "null"],
+            ..plain
+        },
+        "css" => Syntax {
+            block_comment: Some(("/*", "*/")),
+            single_quote_strings: true,
+            ..plain
+        },
+        "html" => Syntax {
+            block_comment: Some(("<!--", "-->")),
+            single_quote_strings: true,
+            tags: true,
+            ..plain
+        },
+        _ => return None,
+    })
+}
+
+/// Returns HTML with `<span class="hljs-*">` tokens.
+/// Code in unknown languages is only escaped.
+pub fn highlight(code: &str, lang: &str) -> String {
+    let syntax = match syntax(lang) {
+        Some(syntax) => syntax,
+        None => return escape(code),
+    };
+
+    let mut html = String::with_capacity(code.len() * 2);
+    let mut index = 0;
+    while let Some(character) = code[index..].chars().next() {
+        let rest = &code[index..];
+        let previous = code[..index].chars().next_back();
+
+        let (class, len) = if let Some(len) = comment_len(rest, &syntax) {
+            (Some("hljs-comment"), len)
+        } else if character == '"'
+            || character == '\'' && syntax.single_quote_strings
+        {
+            (Some("hljs-string"), string_len(rest, character))
+        } else if syntax.rust_items
+            && (rest.starts_with("#[") || rest.starts_with("#!["))
+        {
+            (Some("hljs-meta"), attribute_len(rest))
+        } else if syntax.tags
+            && character == '<'
+            && rest[1..].starts_with(|next: char| {
+                next.is_ascii_alphabetic() || next == '/'
+            })
+        {
+            let prefix_len = if rest[1..].starts_with('/') {
+                2
+            } else {
+                1
+            };
+            html.push_str(&escape(&rest[..prefix_len]));
+            index += prefix_len;
+            let len = word_len(&code[index..], |character| {
+                character.is_ascii_alphanumeric() || character == '-'
+            });
+            (Some("hljs-name"), len)
+        } else if character.is_ascii_digit()
+            && !previous.map_or(false, is_identifier_char)
+        {
+            let len = word_len(rest, |character| {
+                character.is_ascii_alphanumeric()
+                    || character == '_'
+                    || character == '.'
+            });
+            (Some("hljs-number"), len)
+        } else if is_identifier_char(character) {
+            let len = word_len(rest, is_identifier_char);
+            let word = &rest[..len];
+            if syntax.rust_items && rest[len..].starts_with('!') {
+                (Some("hljs-built_in"), len + 1)
+            } else if syntax.keywords.contains(&word) {
+                (Some("hljs-keyword"), len)
+            } else if syntax.literals.contains(&word) {
+                (Some("hljs-literal"), len)
+            } else if syntax.rust_items
+                && word.starts_with(|character: char| character.is_uppercase())
+            {
+                (Some("hljs-type"), len)
+            } else {
+                (None, len)
+            }
+        } else {
+            (None, character.len_utf8())
+        };
+
+        let token = &code[index..index + len];
+        match class {
+            Some(class) => html.push_str(&format!(
+                r#"<span class="{}">{}</span>"#,
+                class,
+                escape(token)
+            )),
+            None => html.push_str(&escape(token)),
+        }
+        index += len;
+    }
+    html
+}
+
+fn comment_len(code: &str, syntax: &Syntax) -> Option<usize> {
+    if let Some(start) = syntax.line_comment {
+        if code.starts_with(start) {
+            return Some(code.find('\n').unwrap_or(code.len()));
+        }
+    }
+    let (start, end) = syntax.block_comment?;
+    if !code.starts_with(start) {
+        return None;
+    }
+    Some(
+        code[start.len()..]
+            .find(end)
+            .map_or(code.len(), |position| start.len() + position + end.len()),
+    )
+}
+
+/// Strings end with the unescaped `quote` or with the code.
+fn string_len(code: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (index, character) in code.char_indices().skip(1) {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if character == quote => return index + 1,
+            _ => (),
+        }
+    }
+    code.len()
+}
+
+/// `#[derive(Clone)]` including nested brackets.
+fn attribute_len(code: &str) -> usize {
+    let mut depth = 0;
+    for (index, character) in code.char_indices() {
+        match character {
+            '[' => depth += 1,
+            ']' if depth == 1 => return index + 1,
+            ']' => depth -= 1,
+            '\n' => return index,
+            _ => (),
+        }
+    }
+    code.len()
+}
+
+fn word_len(code: &str, is_word_char: impl Fn(char) -> bool) -> usize {
+    code.find(|character| !is_word_char(character)).unwrap_or(code.len())
+}
+
+fn is_identifier_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_rust() {
+        assert_eq!(
+            highlight("let model = vec![1]; // TODO", "rust"),
+            concat!(
+                r#"<span class="hljs-keyword">let</span> model = "#,
+                r#"<span class="hljs-built_in">vec!</span>["#,
+                r#"<span class="hljs-number">1</span>]; "#,
+                r#"<span class="hljs-comment">// TODO</span>"#,
+            )
+        );
+        assert_eq!(
+            highlight(r#"#[derive(Default)] struct Model<'a>"#, "rust"),
+            concat!(
+                r#"<span class="hljs-meta">#[derive(Default)]</span> "#,
+                r#"<span class="hljs-keyword">struct</span> "#,
+                r#"<span class="hljs-type">Model</span>&lt;'a&gt;"#,
+            )
+        );
+    }
+
+    #[test]
+    fn highlights_strings_with_escaped_quotes() {
+        assert_eq!(
+            highlight(r#"x = "a \" b" # c"#, "toml"),
+            concat!(
+                r#"x = <span class="hljs-string">"a \" b"</span> "#,
+                r#"<span class="hljs-comment"># c</span>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn highlights_html_tags() {
+        assert_eq!(
+            highlight("<div>a</div>", "html"),
+            concat!(
+                r#"&lt;<span class="hljs-name">div</span>&gt;a"#,
+                r#"&lt;/<span class="hljs-name">div</span>&gt;"#,
+            )
+        );
+    }
+
+    #[test]
+    fn escapes_code_in_unknown_languages() {
+        assert_eq!(highlight("a < b && c", "text"), "a &lt; b &amp;&amp; c");
+    }
+}
@@ -0,0 +1,117 @@
+//! OpenGraph preview images of guides for link previews in chats and forums.
+//! They're rendered from an SVG template with `resvg`.
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+const LOGO: &str = include_str!("../../seed_branding/seed_logo.min.svg");
+/// The logo is 946 x 404.
+const LOGO_ATTRIBUTES: &str = r#"x="80" y="72" width="281" height="120""#;
+/// Fonts are bundled, so images don't depend on fonts of the build machine
+/// (resvg silently skips text without a matching font).
+const FONTS: [&[u8]; 2] = [
+    include_bytes!("fonts/DejaVuSans.ttf"),
+    include_bytes!("fonts/DejaVuSans-Bold.ttf"),
+];
+const FONT_FAMILY: &str = "DejaVu Sans";
+const TITLE_LINE_MAX_CHARS: usize = 22;
+const TITLE_LINE_HEIGHT: usize = 96;
+
+pub struct Renderer {
+    options: usvg::Options,
+}
+
+impl Renderer {
+    /// Panics if the bundled fonts don't contain `FONT_FAMILY`.
+    pub fn new() -> Self {
+        let mut options = usvg::Options::default();
+        for font in FONTS.iter() {
+            options.fontdb.load_font_data(font.to_vec());
+        }
+        // The title is bold, other text has the normal weight.
+        for weight in
+            &[usvg::fontdb::Weight::NORMAL, usvg::fontdb::Weight::BOLD]
+        {
+            assert!(
+                options.fontdb.faces().iter().any(|face| {
+                    face.family == FONT_FAMILY && face.weight == *weight
+                }),
+                "No bundled font face matches `{}` with weight {}",
+                FONT_FAMILY,
+                weight.0
+            );
+        }
+        Self {
+            options,
+        }
+    }
+
+    pub fn render(&self, menu_title: &str, seed_version: &str, path: &str) {
+        let svg = svg(menu_title, seed_version);
+        let tree = usvg::Tree::from_str(&svg, &self.options.to_ref()).unwrap();
+        let mut pixmap = tiny_skia::Pixmap::new(WIDTH, HEIGHT).unwrap();
+        resvg::render(
+            &tree,
+            usvg::FitTo::Original,
+            tiny_skia::Transform::default(),
+            pixmap.as_mut(),
+        )
+        .unwrap();
+        pixmap.save_png(path).unwrap();
+    }
+}
+
+fn svg(menu_title: &str, seed_version: &str) -> String {
+    let title_lines = wrap(menu_title)
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            format!(
+                r#"<tspan x="80" y="{}">{}</tspan>"#,
+                330 + index * TITLE_LINE_HEIGHT,
+                escape(line)
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">
+  <rect width="{width}" height="{height}" fill="#ffffff"/>
+  <rect width="{width}" height="16" fill="#48bb78"/>
+  <rect y="{bottom_bar_y}" width="{width}" height="16" fill="#4299e1"/>
+  {logo}
+  <text font-family="{font_family}" font-size="80" font-weight="bold" fill="#1a202c">{title_lines}</text>
+  <text x="80" y="550" font-family="{font_family}" font-size="40" fill="#2f855a">Seed {seed_version}</text>
+  <text x="1120" y="550" text-anchor="end" font-family="{font_family}" font-size="32" fill="#718096">seed-rs.org</text>
+</svg>
+"##,
+        width = WIDTH,
+        height = HEIGHT,
+        bottom_bar_y = HEIGHT - 16,
+        logo = LOGO.replacen("<svg ", &format!("<svg {} ", LOGO_ATTRIBUTES), 1),
+        font_family = FONT_FAMILY,
+        title_lines = title_lines,
+        seed_version = escape(seed_version),
+    )
+}
+
+/// Splits the title into lines by words.
+fn wrap(title: &str) -> Vec<String> {
+    let mut lines = Vec::<String>::new();
+    for word in title.split_whitespace() {
+        match lines.last_mut() {
+            Some(line)
+                if line.chars().count() + 1 + word.chars().count()
+                    <= TITLE_LINE_MAX_CHARS =>
+            {
+                line.push(' ');
+                line.push_str(word);
+            },
+            _ => lines.push(word.to_owned()),
+        }
+    }
+    lines
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
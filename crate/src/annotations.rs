@@ -0,0 +1,412 @@
+use crate::{
+    generated::css_classes::C,
+    guide::{self, Guide},
+    search::{self, HtmlPart},
+    storage::{self, Migration},
+};
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const STORAGE_KEY: &str = "seed_annotations";
+/// Increment it on every `Annotations` schema change and add a migration.
+const CURRENT_VERSION: u64 = 2;
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+pub const EXPORT_FILE_NAME: &str = "seed-notes.md";
+pub const INDEX_ATTRIBUTE: &str = "data-annotation-index";
+
+// ------ Annotation ------
+
+/// A highlighted quote, optionally with a note.
+/// It's anchored by its quote and the closest heading above it
+/// so it survives small guide edits.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub seed_version: String,
+    pub slug: String,
+    /// `None` if the quote is above the first heading.
+    pub heading_id: Option<String>,
+    pub quote: String,
+    /// `None` for a plain highlight.
+    pub note: Option<String>,
+}
+
+impl Annotation {
+    /// The annotated guide, even if its slug has been aliased.
+    pub fn guide<'a>(&self, guides: &'a [Guide]) -> Option<&'a Guide> {
+        guide::find(guides, &self.seed_version, &self.slug)
+    }
+
+    /// Both annotations mark the same quote; notes may differ.
+    fn has_same_quote(&self, other: &Self) -> bool {
+        self.seed_version == other.seed_version
+            && self.slug == other.slug
+            && self.heading_id == other.heading_id
+            && self.quote == other.quote
+    }
+}
+
+// ------ Annotations ------
+
+#[derive(Serialize, Deserialize)]
+pub struct Annotations {
+    version: u64,
+    annotations: Vec<Annotation>,
+}
+
+impl Default for Annotations {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            annotations: Vec::new(),
+        }
+    }
+}
+
+impl Annotations {
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY, CURRENT_VERSION, MIGRATIONS)
+    }
+
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Annotation> {
+        self.annotations.get(index)
+    }
+
+    /// Index of the annotation with the same quote as `annotation`.
+    pub fn position(&self, annotation: &Annotation) -> Option<usize> {
+        self.annotations
+            .iter()
+            .position(|other| other.has_same_quote(annotation))
+    }
+
+    /// Annotations of the guide with their indices.
+    pub fn of_guide<'a>(
+        &'a self,
+        guide: &'a Guide,
+        guides: &'a [Guide],
+    ) -> impl Iterator<Item = (usize, &'a Annotation)> {
+        self.annotations.iter().enumerate().filter(move |(_, annotation)| {
+            annotation.guide(guides) == Some(guide)
+        })
+    }
+
+    /// Returns the index of the new annotation.
+    pub fn add(&mut self, guide: &Guide, selection: TextSelection) -> usize {
+        self.annotations.push(Annotation {
+            seed_version: guide.seed_version.to_owned(),
+            slug: guide.slug.to_owned(),
+            heading_id: selection.heading_id,
+            quote: selection.quote,
+            note: None,
+        });
+        self.annotations.len() - 1
+    }
+
+    /// A blank note turns the annotation into a plain highlight.
+    pub fn set_note(&mut self, index: usize, note: &str) {
+        if let Some(annotation) = self.annotations.get_mut(index) {
+            let note = note.trim();
+            annotation.note = if note.is_empty() {
+                None
+            } else {
+                Some(note.to_owned())
+            };
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.annotations.len() {
+            self.annotations.remove(index);
+        }
+    }
+
+    /// All annotations grouped by guides in the menu order.
+    pub fn to_markdown(&self, guides: &[Guide]) -> String {
+        let mut markdown = "# Seed guide notes\n".to_owned();
+        for guide in guides {
+            let mut annotations = self.of_guide(guide, guides).peekable();
+            if annotations.peek().is_none() {
+                continue;
+            }
+            markdown.push_str(&format!(
+                "\n## {} (Seed {})\n",
+                guide.menu_title, guide.seed_version
+            ));
+            for (_, annotation) in annotations {
+                push_annotation_markdown(&mut markdown, annotation, guide);
+            }
+        }
+        markdown
+    }
+}
+
+fn push_annotation_markdown(
+    markdown: &mut String,
+    annotation: &Annotation,
+    guide: &Guide,
+) {
+    markdown.push('\n');
+    if let Some(heading) = annotation
+        .heading_id
+        .as_ref()
+        .and_then(|id| guide.headings().find(|heading| heading.id == id))
+    {
+        markdown.push_str(&format!("*{}*\n\n", heading.title));
+    }
+    for line in annotation.quote.lines() {
+        markdown.push_str(&format!("> {}\n", line));
+    }
+    if let Some(note) = &annotation.note {
+        markdown.push_str(&format!("\n{}\n", note));
+    }
+}
+
+// ------ migrations ------
+
+/// Replaces outdated heading ids (see `guide::current_heading_id`).
+fn migrate_v1_to_v2(annotations: &mut Map<String, Value>) {
+    let annotations = match annotations.get_mut("annotations") {
+        Some(Value::Array(annotations)) => annotations,
+        _ => return,
+    };
+    for annotation in annotations {
+        if let Some(Value::String(heading_id)) =
+            annotation.get_mut("heading_id")
+        {
+            *heading_id = guide::current_heading_id(heading_id);
+        }
+    }
+}
+
+// ------ TextSelection ------
+
+/// Text selected in the guide content, not saved yet.
+pub struct TextSelection {
+    pub heading_id: Option<String>,
+    pub quote: String,
+}
+
+/// Returns the non-empty text selection inside `container`.
+pub fn text_selection(
+    container: &web_sys::Node,
+    guide: &Guide,
+) -> Option<TextSelection> {
+    let selection = window().get_selection().ok()??;
+    if selection.is_collapsed() {
+        return None;
+    }
+    let range = selection.get_range_at(0).ok()?;
+    if !container.contains(Some(&range.common_ancestor_container().ok()?)) {
+        return None;
+    }
+    // Code is rendered by `<code-block>`, it isn't in the guide HTML text
+    // where quotes are searched.
+    let code_blocks = container
+        .dyn_ref::<web_sys::Element>()?
+        .query_selector_all("code-block")
+        .ok()?;
+    if (0..code_blocks.length())
+        .filter_map(|index| code_blocks.get(index))
+        .any(|code_block| range.intersects_node(&code_block).unwrap_or(true))
+    {
+        return None;
+    }
+    let quote = selected_text(&range)?.trim().to_owned();
+    if quote.is_empty() {
+        return None;
+    }
+    let start = range.start_container().ok()?;
+    // The last heading placed before the selection start (or containing it).
+    let heading_id = guide
+        .headings()
+        .filter(|heading| {
+            document().get_element_by_id(heading.id).map_or(false, |element| {
+                element.compare_document_position(&start)
+                    & web_sys::Node::DOCUMENT_POSITION_FOLLOWING
+                    != 0
+            })
+        })
+        .last()
+        .map(|heading| heading.id.to_owned());
+    Some(TextSelection {
+        heading_id,
+        quote,
+    })
+}
+
+/// Text of the range without heading bookmark buttons.
+fn selected_text(range: &web_sys::Range) -> Option<String> {
+    let contents = range.clone_contents().ok()?;
+    let buttons = contents
+        .query_selector_all(&format!(".{}", C.heading_bookmark))
+        .ok()?;
+    for index in 0..buttons.length() {
+        if let Some(button) = buttons.get(index) {
+            if let Some(parent) = button.parent_node() {
+                parent.remove_child(&button).ok()?;
+            }
+        }
+    }
+    contents.text_content()
+}
+
+pub fn clear_text_selection() {
+    if let Ok(Some(selection)) = window().get_selection() {
+        selection.remove_all_ranges().expect("remove selection ranges");
+    }
+}
+
+// ------ mark in HTML ------
+
+/// Wraps annotated quotes in the text content of the HTML
+/// with `<mark class="annotation" data-annotation-index="{index}">`.
+/// A quote is searched below its heading first, then in the whole HTML.
+/// Quotes that can't be found or overlap another annotation aren't marked.
+pub fn mark_annotations_in_html<'a>(
+    html: &str,
+    annotations: impl Iterator<Item = (usize, &'a Annotation)>,
+) -> String {
+    let parts = search::html_parts(html);
+    let text = parts
+        .iter()
+        .filter_map(|part| match part {
+            HtmlPart::Tag(_) => None,
+            HtmlPart::Text(text) => Some(*text),
+        })
+        .collect::<String>();
+
+    let mut ranges: Vec<(usize, usize, usize, &Annotation)> = Vec::new();
+    for (index, annotation) in annotations {
+        let quote = escape_html(&annotation.quote);
+        let heading_offset = annotation
+            .heading_id
+            .as_ref()
+            .and_then(|heading_id| heading_text_offset(&parts, heading_id))
+            .unwrap_or_default();
+        let range = find_quote(&text, &quote, heading_offset)
+            .or_else(|| find_quote(&text, &quote, 0));
+        if let Some((start, end)) = range {
+            let overlaps = ranges.iter().any(|(other_start, other_end, ..)| {
+                start < *other_end && *other_start < end
+            });
+            if !overlaps {
+                ranges.push((start, end, index, annotation));
+            }
+        }
+    }
+    ranges.sort_by_key(|(start, ..)| *start);
+
+    let mut marked_html = String::with_capacity(html.len());
+    let mut text_offset = 0;
+    for part in parts {
+        match part {
+            HtmlPart::Tag(tag) => marked_html.push_str(tag),
+            HtmlPart::Text(text) => {
+                let text_end = text_offset + text.len();
+                let mut position = text_offset;
+                for (start, end, index, annotation) in &ranges {
+                    if *end <= text_offset || *start >= text_end {
+                        continue;
+                    }
+                    let start = (*start).max(text_offset);
+                    let end = (*end).min(text_end);
+                    marked_html.push_str(
+                        &text[position - text_offset..start - text_offset],
+                    );
+                    marked_html.push_str(&mark_tag(*index, annotation));
+                    marked_html.push_str(
+                        &text[start - text_offset..end - text_offset],
+                    );
+                    marked_html.push_str("</mark>");
+                    position = end;
+                }
+                marked_html.push_str(&text[position - text_offset..]);
+                text_offset = text_end;
+            },
+        }
+    }
+    marked_html
+}
+
+fn mark_tag(index: usize, annotation: &Annotation) -> String {
+    match &annotation.note {
+        None => format!(
+            r#"<mark class="{}" {}="{}">"#,
+            C.annotation, INDEX_ATTRIBUTE, index
+        ),
+        Some(note) => format!(
+            r#"<mark class="{} {}" {}="{}" title="{}">"#,
+            C.annotation,
+            C.annotation_note,
+            INDEX_ATTRIBUTE,
+            index,
+            escape_html(note)
+        ),
+    }
+}
+
+/// Offset in the concatenated text content where the heading starts.
+fn heading_text_offset(parts: &[HtmlPart], heading_id: &str) -> Option<usize> {
+    let id_attribute = format!(r#" id="{}""#, heading_id);
+    let mut text_offset = 0;
+    for part in parts {
+        match part {
+            HtmlPart::Tag(tag) if tag.contains(&id_attribute) => {
+                return Some(text_offset);
+            },
+            HtmlPart::Tag(_) => (),
+            HtmlPart::Text(text) => text_offset += text.len(),
+        }
+    }
+    None
+}
+
+/// Byte range of `quote` in `text`, searched from the byte `from`.
+/// Whitespace sequences are compared as a single space,
+/// because selected text and HTML differ in line breaks.
+fn find_quote(text: &str, quote: &str, from: usize) -> Option<(usize, usize)> {
+    let (normalized_quote, _) = normalize_whitespace(quote);
+    let normalized_quote = normalized_quote.trim();
+    if normalized_quote.is_empty() {
+        return None;
+    }
+    let (normalized_text, positions) = normalize_whitespace(&text[from..]);
+    let start = normalized_text.find(normalized_quote)?;
+    let end = start + normalized_quote.len();
+    // The quote is trimmed, so its last byte isn't a collapsed whitespace.
+    Some((from + positions[start], from + positions[end - 1] + 1))
+}
+
+/// Collapses whitespace sequences into a single space.
+/// Returns also the original byte position of each normalized byte.
+fn normalize_whitespace(text: &str) -> (String, Vec<usize>) {
+    let mut normalized = String::with_capacity(text.len());
+    let mut positions = Vec::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for (index, character) in text.char_indices() {
+        if character.is_whitespace() {
+            if !in_whitespace {
+                normalized.push(' ');
+                positions.push(index);
+            }
+            in_whitespace = true;
+        } else {
+            normalized.push(character);
+            positions.extend(index..index + character.len_utf8());
+            in_whitespace = false;
+        }
+    }
+    (normalized, positions)
+}
+
+/// Escapes text the same way as `pulldown-cmark` does in `build.rs`.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,63 @@
+//! Writes prerendered pages into `dist` built by webpack:
+//! `index.html` for each path, `404.html`, `sitemap.xml` and `robots.txt`.
+//!
+//! `cargo run --manifest-path crate/Cargo.toml --bin prerender --release`
+
+use seed_rs_org::{
+    prerender::{self, PrerenderedPage},
+    sitemap,
+};
+use std::{fs, path::PathBuf};
+
+const DIST_FOLDER: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../dist");
+const APP_ELEMENT_START: &str = r#"<section id="app">"#;
+const APP_ELEMENT_END: &str = "</section>";
+const TITLE_ELEMENT: &str = "<title>Seed</title>";
+const HEAD_END: &str = "</head>";
+
+fn main() {
+    let template = fs::read_to_string(format!("{}/index.html", DIST_FOLDER))
+        .expect("read `dist/index.html`, run `yarn build:release` first");
+
+    for path in prerender::paths() {
+        let mut file_path = PathBuf::from(DIST_FOLDER);
+        file_path.extend(&path);
+        fs::create_dir_all(&file_path).unwrap();
+        file_path.push("index.html");
+        let page = prerender::render(&path);
+        fs::write(&file_path, fill_template(&template, &page)).unwrap();
+        println!("/{}", path.join("/"));
+    }
+
+    let page = prerender::render_not_found();
+    fs::write(
+        format!("{}/404.html", DIST_FOLDER),
+        fill_template(&template, &page),
+    )
+    .unwrap();
+    println!("404");
+
+    fs::write(format!("{}/sitemap.xml", DIST_FOLDER), sitemap::sitemap_xml())
+        .unwrap();
+    fs::write(format!("{}/robots.txt", DIST_FOLDER), sitemap::robots_txt())
+        .unwrap();
+    println!("sitemap.xml, robots.txt");
+}
+
+fn fill_template(template: &str, page: &PrerenderedPage) -> String {
+    let content_start =
+        template.find(APP_ELEMENT_START).expect("find the app element")
+            + APP_ELEMENT_START.len();
+    let content_end = content_start
+        + template[content_start..]
+            .find(APP_ELEMENT_END)
+            .expect("find the app element end");
+    format!(
+        "{}{}{}",
+        &template[..content_start],
+        page.html,
+        &template[content_end..]
+    )
+    .replacen(TITLE_ELEMENT, &format!("<title>{}</title>", page.title), 1)
+    .replacen(HEAD_END, &format!("{}{}", page.head, HEAD_END), 1)
+}
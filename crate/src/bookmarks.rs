@@ -0,0 +1,320 @@
+use crate::{
+    generated::css_classes::C,
+    guide::{self, Guide, Heading},
+    search::{self, HtmlPart},
+    storage::{self, Migration},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+pub const STORAGE_KEY: &str = "seed_bookmarks";
+/// Increment it on every `Bookmarks` schema change and add a migration.
+const CURRENT_VERSION: u64 = 2;
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+pub const EXPORT_FILE_NAME: &str = "seed-bookmarks.json";
+
+// ------ Bookmark ------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub seed_version: String,
+    pub slug: String,
+    /// `None` if the whole guide is bookmarked.
+    pub heading_id: Option<String>,
+    /// Title at the time of bookmarking.
+    /// It's displayed if the guide doesn't exist anymore.
+    pub title: String,
+}
+
+impl Bookmark {
+    fn new(guide: &Guide, heading: Option<Heading>) -> Self {
+        Self {
+            seed_version: guide.seed_version.to_owned(),
+            slug: guide.slug.to_owned(),
+            heading_id: heading.map(|heading| heading.id.to_owned()),
+            title: heading.map_or_else(
+                || guide.menu_title.to_owned(),
+                |heading| format!("{} › {}", guide.menu_title, heading.title),
+            ),
+        }
+    }
+
+    /// The bookmarked guide, even if its slug has been aliased.
+    pub fn guide<'a>(&self, guides: &'a [Guide]) -> Option<&'a Guide> {
+        guide::find(guides, &self.seed_version, &self.slug)
+    }
+
+    fn points_to(
+        &self,
+        guide: &Guide,
+        heading_id: Option<&str>,
+        guides: &[Guide],
+    ) -> bool {
+        self.heading_id.as_deref() == heading_id
+            && self.guide(guides) == Some(guide)
+    }
+
+    fn has_same_target(&self, other: &Self) -> bool {
+        self.seed_version == other.seed_version
+            && self.current_slug() == other.current_slug()
+            && self.heading_id == other.heading_id
+    }
+
+    fn current_slug(&self) -> &str {
+        guide::current_slug(&self.seed_version, &self.slug)
+    }
+}
+
+// ------ Bookmarks ------
+
+#[derive(Serialize, Deserialize)]
+pub struct Bookmarks {
+    version: u64,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl Default for Bookmarks {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            bookmarks: Vec::new(),
+        }
+    }
+}
+
+impl Bookmarks {
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY, CURRENT_VERSION, MIGRATIONS)
+    }
+
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    pub fn all(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn contains(
+        &self,
+        guide: &Guide,
+        heading_id: Option<&str>,
+        guides: &[Guide],
+    ) -> bool {
+        self.bookmarks
+            .iter()
+            .any(|bookmark| bookmark.points_to(guide, heading_id, guides))
+    }
+
+    /// Adds the bookmark or removes it if it already exists.
+    pub fn toggle(
+        &mut self,
+        guide: &Guide,
+        heading: Option<Heading>,
+        guides: &[Guide],
+    ) {
+        let heading_id = heading.map(|heading| heading.id);
+        let bookmark_count = self.bookmarks.len();
+        self.bookmarks
+            .retain(|bookmark| !bookmark.points_to(guide, heading_id, guides));
+        if self.bookmarks.len() == bookmark_count {
+            self.bookmarks.push(Bookmark::new(guide, heading));
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.bookmarks.len() {
+            self.bookmarks.remove(index);
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("serialize bookmarks")
+    }
+
+    /// Adds bookmarks exported by `to_json`, skips duplicates.
+    /// Aliased slugs are replaced with the current ones.
+    /// Returns the number of added bookmarks.
+    pub fn import(&mut self, json: &str) -> Result<usize, String> {
+        let json =
+            serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let imported: Self =
+            storage::from_json(json, CURRENT_VERSION, MIGRATIONS)?;
+        let bookmark_count = self.bookmarks.len();
+        for mut bookmark in imported.bookmarks {
+            bookmark.slug = bookmark.current_slug().to_owned();
+            if !self
+                .bookmarks
+                .iter()
+                .any(|existing| existing.has_same_target(&bookmark))
+            {
+                self.bookmarks.push(bookmark);
+            }
+        }
+        Ok(self.bookmarks.len() - bookmark_count)
+    }
+}
+
+// ------ migrations ------
+
+/// Replaces outdated heading ids (see `guide::current_heading_id`).
+fn migrate_v1_to_v2(bookmarks: &mut Map<String, Value>) {
+    let bookmarks = match bookmarks.get_mut("bookmarks") {
+        Some(Value::Array(bookmarks)) => bookmarks,
+        _ => return,
+    };
+    for bookmark in bookmarks {
+        if let Some(Value::String(heading_id)) = bookmark.get_mut("heading_id")
+        {
+            *heading_id = guide::current_heading_id(heading_id);
+        }
+    }
+}
+
+// ------ heading buttons ------
+
+/// Adds a bookmark button to every heading in the guide HTML.
+/// Clicks are handled in `page::guide::view_guide_html`.
+pub fn add_heading_buttons(
+    html: &str,
+    guide: &Guide,
+    bookmarks: &Bookmarks,
+    guides: &[Guide],
+) -> String {
+    let mut headings = guide
+        .headings()
+        .map(|heading| {
+            let heading_tag =
+                format!(r#"<h{} id="{}">"#, heading.level, heading.id);
+            (heading_tag, heading)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut output = String::with_capacity(html.len());
+    for part in search::html_parts(html) {
+        match part {
+            HtmlPart::Tag(tag) => {
+                output.push_str(tag);
+                if let Some(heading) = headings.remove(tag) {
+                    let bookmarked =
+                        bookmarks.contains(guide, Some(heading.id), guides);
+                    output.push_str(&heading_button(heading, bookmarked));
+                }
+            },
+            HtmlPart::Text(text) => output.push_str(text),
+        }
+    }
+    output
+}
+
+fn heading_button(heading: Heading, bookmarked: bool) -> String {
+    format!(
+        r#"<button class="{}" data-heading-id="{}" title="Bookmark this section" aria-pressed="{}">{}</button>"#,
+        if bookmarked {
+            format!("{} {}", C.heading_bookmark, C.heading_bookmark_active)
+        } else {
+            C.heading_bookmark.to_owned()
+        },
+        heading.id,
+        bookmarked,
+        if bookmarked {
+            "\u{2605}"
+        } else {
+            "\u{2606}"
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmark(seed_version: &str, slug: &str) -> Bookmark {
+        Bookmark {
+            seed_version: seed_version.to_owned(),
+            slug: slug.to_owned(),
+            heading_id: None,
+            title: slug.to_owned(),
+        }
+    }
+
+    fn export(bookmarks: Vec<Bookmark>) -> String {
+        Bookmarks {
+            version: CURRENT_VERSION,
+            bookmarks,
+        }
+        .to_json()
+    }
+
+    #[test]
+    fn import_replaces_aliased_slugs() {
+        let guides = guide::guides();
+        let guide = guide::find(&guides, "0.6.0", "http-requests-and-state")
+            .unwrap();
+        let mut bookmarks = Bookmarks::default();
+
+        let json = export(vec![bookmark("0.6.0", "fetch")]);
+        assert_eq!(bookmarks.import(&json), Ok(1));
+        assert_eq!(bookmarks.all()[0].slug, "http-requests-and-state");
+        assert!(bookmarks.contains(guide, None, &guides));
+    }
+
+    #[test]
+    fn import_skips_bookmarks_with_aliased_slugs_of_existing_ones() {
+        let mut bookmarks = Bookmarks {
+            version: CURRENT_VERSION,
+            bookmarks: vec![bookmark("0.6.0", "http-requests-and-state")],
+        };
+
+        let json = export(vec![
+            bookmark("0.6.0", "fetch"),
+            bookmark("0.7.0", "fetch"),
+        ]);
+        assert_eq!(bookmarks.import(&json), Ok(1));
+        assert_eq!(bookmarks.all()[1].seed_version, "0.7.0");
+        assert_eq!(bookmarks.all()[1].slug, "fetch");
+    }
+
+    #[test]
+    fn migrates_v1_heading_ids() {
+        let json = serde_json::json!({
+            "bookmarks": [
+                {
+                    "seed_version": "0.7.0",
+                    "slug": "todomvc_model",
+                    "heading_id": "todomvc---model",
+                    "title": "TodoMVC - Model",
+                },
+                {
+                    "seed_version": "0.7.0",
+                    "slug": "todomvc_model",
+                    "heading_id": null,
+                    "title": "TodoMVC - Model",
+                },
+            ],
+        });
+        let bookmarks: Bookmarks =
+            storage::from_json(json, CURRENT_VERSION, MIGRATIONS).unwrap();
+
+        assert_eq!(bookmarks.version, CURRENT_VERSION);
+        assert_eq!(
+            bookmarks.all()[0].heading_id.as_deref(),
+            Some("todomvc-model")
+        );
+        assert!(bookmarks.all()[1].heading_id.is_none());
+    }
+
+    #[test]
+    fn contains_bookmark_with_aliased_slug() {
+        let guides = guide::guides();
+        let guide = guide::find(&guides, "0.6.0", "misc").unwrap();
+        let bookmarks = Bookmarks {
+            version: CURRENT_VERSION,
+            bookmarks: vec![bookmark("0.6.0", "misc")],
+        };
+
+        assert_eq!(guide.slug, "misc-features");
+        assert!(bookmarks.contains(guide, None, &guides));
+    }
+}
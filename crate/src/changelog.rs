@@ -0,0 +1,172 @@
+use std::fmt;
+
+/// Changelog entries parsed from the Seed changelog, one per line.
+/// (see `build/changelog.rs`)
+const CHANGELOG: &str = include_str!("../generated_guides/changelog.txt");
+
+// ------ Version ------
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Version {
+    major: u16,
+    minor: u16,
+    patch: u16,
+}
+
+impl Version {
+    /// `0.6.0`
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.').map(str::parse);
+        let version = Self {
+            major: parts.next()?.ok()?,
+            minor: parts.next()?.ok()?,
+            patch: parts.next().unwrap_or(Ok(0)).ok()?,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(version)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// ------ EntryKind ------
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EntryKind {
+    Breaking,
+    Feature,
+    Fix,
+    Other,
+}
+
+impl EntryKind {
+    pub const ALL: [Self; 4] =
+        [Self::Breaking, Self::Feature, Self::Fix, Self::Other];
+
+    /// Used in `changelog.txt` and in URLs.
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Breaking => "breaking",
+            Self::Feature => "feature",
+            Self::Fix => "fix",
+            Self::Other => "other",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Breaking => "Breaking changes",
+            Self::Feature => "Features",
+            Self::Fix => "Fixes",
+            Self::Other => "Other",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.iter().find(|kind| kind.id() == id).copied()
+    }
+}
+
+// ------ Release ------
+
+pub struct Release {
+    pub version: Version,
+    /// `2020-02-01`
+    pub date: Option<&'static str>,
+    pub entries: Vec<Entry>,
+}
+
+pub struct Entry {
+    pub kind: EntryKind,
+    pub html: &'static str,
+}
+
+// ------ Filter ------
+
+/// Version range (inclusive) and entry kind, `None` means unrestricted.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct Filter {
+    pub from: Option<Version>,
+    pub to: Option<Version>,
+    pub kind: Option<EntryKind>,
+}
+
+impl Filter {
+    fn contains(&self, version: Version) -> bool {
+        self.from.map_or(true, |from| version >= from)
+            && self.to.map_or(true, |to| version <= to)
+    }
+}
+
+// ------ releases ------
+
+/// All releases, the newest first.
+pub fn releases() -> Vec<Release> {
+    let mut releases = Vec::<Release>::new();
+    for line in CHANGELOG.lines() {
+        let mut parts = line.splitn(4, '\t');
+        let (version, date, kind, html) =
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(version), Some(date), Some(kind), Some(html)) => {
+                    (version, date, kind, html)
+                },
+                _ => continue,
+            };
+        let (version, kind) =
+            match (Version::parse(version), EntryKind::from_id(kind)) {
+                (Some(version), Some(kind)) => (version, kind),
+                _ => continue,
+            };
+        let entry = Entry {
+            kind,
+            html,
+        };
+        match releases.last_mut() {
+            Some(release) if release.version == version => {
+                release.entries.push(entry);
+            },
+            _ => releases.push(Release {
+                version,
+                date: Some(date).filter(|date| !date.is_empty()),
+                entries: vec![entry],
+            }),
+        }
+    }
+    releases
+}
+
+/// Releases in the filter's version range with entries of its kind.
+/// Releases without such entries are omitted.
+pub fn filtered_releases(filter: Filter) -> Vec<Release> {
+    releases()
+        .into_iter()
+        .filter(|release| filter.contains(release.version))
+        .filter_map(|mut release| {
+            if let Some(kind) = filter.kind {
+                release.entries.retain(|entry| entry.kind == kind);
+            }
+            if release.entries.is_empty() {
+                None
+            } else {
+                Some(release)
+            }
+        })
+        .collect()
+}
+
+/// Number of entries of the kind in the filter's version range.
+pub fn count(filter: Filter, kind: Option<EntryKind>) -> usize {
+    filtered_releases(Filter {
+        kind,
+        ..filter
+    })
+    .iter()
+    .map(|release| release.entries.len())
+    .sum()
+}
@@ -0,0 +1,19 @@
+//! `<code-block lang="xx" code="xx"></code-block>` elements generated
+//! by `build.rs` from markdown code blocks.
+//!
+//! The module is also included in `build.rs` for EPUBs (see `build/epub.rs`).
+
+/// Start of the opening tag.
+pub const START: &str = "<code-block";
+
+/// Decoded value of the attribute `name` if the tag opens a `<code-block>`.
+/// `build.rs` escapes only `"` in the attribute values.
+pub fn attribute(tag: &str, name: &str) -> Option<String> {
+    if !tag.starts_with(START) {
+        return None;
+    }
+    let prefix = format!(r#" {}=""#, name);
+    let start = tag.find(&prefix)? + prefix.len();
+    let len = tag[start..].find('"')?;
+    Some(tag[start..start + len].replace("&quot;", "\""))
+}
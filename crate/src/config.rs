@@ -0,0 +1,322 @@
+use crate::{
+    generated::css_classes::C,
+    shortcut,
+    storage::{self, Migration},
+    Mode, DEFAULT_SEED_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const STORAGE_KEY: &str = "seed";
+/// Increment it on every `Config` schema change and add a migration.
+const CURRENT_VERSION: u64 = 3;
+/// Configs saved before versioning don't have `version` and are version 1.
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2, migrate_v2_to_v3];
+
+// ------ Config ------
+
+#[derive(Serialize, Deserialize)]
+pub struct Config {
+    pub version: u64,
+    pub mode: Mode,
+    pub settings: Settings,
+    pub shortcut_overrides: BTreeMap<shortcut::Action, String>,
+}
+
+impl Config {
+    pub const fn new(
+        mode: Mode,
+        settings: Settings,
+        shortcut_overrides: BTreeMap<shortcut::Action, String>,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            mode,
+            settings,
+            shortcut_overrides,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new(Mode::default(), Settings::default(), BTreeMap::new())
+    }
+}
+
+/// Loads the stored config and migrates it to the current version.
+pub fn load() -> Config {
+    storage::load(STORAGE_KEY, CURRENT_VERSION, MIGRATIONS)
+}
+
+pub fn save(config: &Config) {
+    storage::save(STORAGE_KEY, config);
+}
+
+// ------ migrations ------
+
+/// Adds `version` and `settings`.
+/// `shortcut_overrides` was optional in version 1.
+fn migrate_v1_to_v2(config: &mut Map<String, Value>) {
+    config
+        .entry("shortcut_overrides")
+        .or_insert_with(|| Value::Object(Map::new()));
+    config.insert(
+        "settings".to_owned(),
+        serde_json::to_value(Settings::default())
+            .expect("serialize default settings"),
+    );
+}
+
+/// Adds `settings.offline_versions`.
+fn migrate_v2_to_v3(config: &mut Map<String, Value>) {
+    if let Some(Value::Object(settings)) = config.get_mut("settings") {
+        settings.insert(
+            "offline_versions".to_owned(),
+            serde_json::to_value(Settings::default().offline_versions)
+                .expect("serialize default offline versions"),
+        );
+    }
+}
+
+// ------ Settings ------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub font_size: FontSize,
+    pub content_width: ContentWidth,
+    pub code_theme: CodeTheme,
+    /// Disables animations and smooth scrolling.
+    pub reduced_motion: bool,
+    /// Seed versions whose guides are cached for offline reading.
+    pub offline_versions: BTreeSet<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            font_size: FontSize::default(),
+            content_width: ContentWidth::default(),
+            code_theme: CodeTheme::default(),
+            reduced_motion: false,
+            offline_versions: vec![DEFAULT_SEED_VERSION.version().to_owned()]
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+// ------ FontSize ------
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FontSize {
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl FontSize {
+    pub const ALL: &'static [Self] =
+        &[Self::Small, Self::Medium, Self::Large, Self::ExtraLarge];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+            Self::ExtraLarge => "Extra large",
+        }
+    }
+
+    /// Font size of the guide content.
+    pub const fn css_value(self) -> &'static str {
+        match self {
+            Self::Small => "14px",
+            Self::Medium => "16px",
+            Self::Large => "18px",
+            Self::ExtraLarge => "20px",
+        }
+    }
+}
+
+impl Default for FontSize {
+    fn default() -> Self {
+        Self::Medium
+    }
+}
+
+// ------ ContentWidth ------
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContentWidth {
+    Narrow,
+    Medium,
+    Wide,
+    Full,
+}
+
+impl ContentWidth {
+    pub const ALL: &'static [Self] =
+        &[Self::Narrow, Self::Medium, Self::Wide, Self::Full];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Narrow => "Narrow",
+            Self::Medium => "Medium",
+            Self::Wide => "Wide",
+            Self::Full => "Full",
+        }
+    }
+
+    /// Max width of the guide content.
+    pub const fn css_value(self) -> &'static str {
+        match self {
+            Self::Narrow => "35rem",
+            Self::Medium => "45rem",
+            Self::Wide => "60rem",
+            Self::Full => "none",
+        }
+    }
+}
+
+impl Default for ContentWidth {
+    fn default() -> Self {
+        Self::Medium
+    }
+}
+
+// ------ CodeTheme ------
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CodeTheme {
+    /// Code colors of the selected `Mode`.
+    Site,
+    Light,
+    Dark,
+}
+
+impl CodeTheme {
+    pub const ALL: &'static [Self] = &[Self::Site, Self::Light, Self::Dark];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Site => "Same as site",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+        }
+    }
+
+    /// Class with code color tokens. (see `css/themes.css`)
+    pub fn class(self) -> Option<&'static str> {
+        match self {
+            Self::Site => None,
+            Self::Light => Some(C.code_theme_light),
+            Self::Dark => Some(C.code_theme_dark),
+        }
+    }
+}
+
+impl Default for CodeTheme {
+    fn default() -> Self {
+        Self::Site
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn from_json(config: Value) -> Result<Config, String> {
+        storage::from_json(config, CURRENT_VERSION, MIGRATIONS)
+    }
+
+    #[test]
+    fn migrates_v1_to_current_version() {
+        let config = from_json(json!({
+            "mode": "Dark",
+            "shortcut_overrides": { "NextGuide": "ctrl+j" },
+        }))
+        .unwrap();
+
+        assert_eq!(config.version, CURRENT_VERSION);
+        assert!(config.mode == Mode::Dark);
+        assert_eq!(
+            config.shortcut_overrides.get(&shortcut::Action::NextGuide),
+            Some(&"ctrl+j".to_owned())
+        );
+        assert!(config.settings.font_size == FontSize::default());
+        assert!(!config.settings.reduced_motion);
+        assert_eq!(
+            config.settings.offline_versions,
+            Settings::default().offline_versions
+        );
+    }
+
+    #[test]
+    fn migrates_v1_without_shortcut_overrides() {
+        let config = from_json(json!({ "mode": "Light" })).unwrap();
+
+        assert!(config.mode == Mode::Light);
+        assert!(config.shortcut_overrides.is_empty());
+    }
+
+    #[test]
+    fn migrates_v2_to_current_version() {
+        let config = from_json(json!({
+            "version": 2,
+            "mode": "HighContrast",
+            "settings": {
+                "font_size": "Large",
+                "content_width": "Wide",
+                "code_theme": "Dark",
+                "tab_group_choices": { "build_tool": "cargo-make" },
+                "reduced_motion": true,
+            },
+            "shortcut_overrides": {},
+        }))
+        .unwrap();
+
+        assert_eq!(config.version, CURRENT_VERSION);
+        assert!(config.mode == Mode::HighContrast);
+        assert!(config.settings.font_size == FontSize::Large);
+        assert!(config.settings.content_width == ContentWidth::Wide);
+        assert!(config.settings.code_theme == CodeTheme::Dark);
+        assert!(config.settings.reduced_motion);
+        assert_eq!(
+            config.settings.offline_versions,
+            Settings::default().offline_versions
+        );
+    }
+
+    #[test]
+    fn loads_current_version_unchanged() {
+        let config = from_json(json!({
+            "version": CURRENT_VERSION,
+            "mode": "Dark",
+            "settings": {
+                "font_size": "Small",
+                "content_width": "Narrow",
+                "code_theme": "Light",
+                "reduced_motion": false,
+                "offline_versions": [],
+            },
+            "shortcut_overrides": {},
+        }))
+        .unwrap();
+
+        assert!(config.mode == Mode::Dark);
+        assert!(config.settings.font_size == FontSize::Small);
+        assert!(config.settings.offline_versions.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(from_json(json!([])).is_err());
+        assert!(from_json(json!({ "version": 0 })).is_err());
+        assert!(from_json(json!({ "mode": "Sepia" })).is_err());
+        assert!(from_json(json!({ "version": 2, "mode": "Dark" })).is_err());
+    }
+}
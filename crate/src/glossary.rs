@@ -0,0 +1,54 @@
+use crate::{
+    guide::{self, Guide},
+    SeedVersion, SEED_VERSIONS,
+};
+
+/// Glossary terms parsed from `glossary.md`, one per line.
+/// (see `build/glossary.rs`)
+const GLOSSARY: &str = include_str!("../generated_guides/glossary.txt");
+
+pub struct Term {
+    /// Anchor on the glossary page.
+    pub id: &'static str,
+    pub html: &'static str,
+    /// Slug of the guide that explains the term.
+    pub guide_slug: &'static str,
+    pub heading_id: Option<&'static str>,
+    pub definition_html: &'static str,
+}
+
+impl Term {
+    /// The explaining guide in the Seed version,
+    /// or in the newest Seed version that has it.
+    pub fn guide<'a>(
+        &self,
+        guides: &'a [Guide],
+        seed_version: SeedVersion,
+    ) -> Option<&'a Guide> {
+        guide::find(guides, seed_version.version(), self.guide_slug).or_else(
+            || {
+                SEED_VERSIONS.iter().find_map(|seed_version| {
+                    guide::find(guides, seed_version.version(), self.guide_slug)
+                })
+            },
+        )
+    }
+}
+
+/// All terms in the order of `glossary.md`.
+pub fn terms() -> Vec<Term> {
+    GLOSSARY
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(5, '\t');
+            Some(Term {
+                id: parts.next()?,
+                html: parts.next()?,
+                guide_slug: parts.next()?,
+                heading_id: Some(parts.next()?)
+                    .filter(|heading_id| !heading_id.is_empty()),
+                definition_html: parts.next()?,
+            })
+        })
+        .collect()
+}
@@ -1,3 +1,5 @@
+use crate::SEED_VERSIONS;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Guide {
     pub seed_version: &'static str,
@@ -5,16 +7,97 @@ pub struct Guide {
     pub menu_title: &'static str,
     pub html: &'static str,
     pub lowercase_text: &'static str,
+    pub text: &'static str,
+    pub outline: &'static str,
+    /// From the front matter or the first paragraph.
+    pub description: &'static str,
+    /// OpenGraph image rendered by `build.rs`.
+    pub og_image_path: &'static str,
     pub prepend_menu_divider: bool,
     pub edit_url: &'static str,
 }
 
+impl Guide {
+    /// Headings parsed from `outline` generated by `build.rs`.
+    pub fn headings(&self) -> impl Iterator<Item = Heading> {
+        self.outline.lines().filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            Some(Heading {
+                level: parts.next()?.parse().ok()?,
+                id: parts.next()?,
+                title: parts.next()?,
+            })
+        })
+    }
+}
+
 impl PartialEq for Guide {
     fn eq(&self, other: &Self) -> bool {
         self.slug == other.slug && self.seed_version == other.seed_version
     }
 }
 
+/// Renamed guides as `(seed_version, old_slug, new_slug)`.
+/// Old URLs and bookmarks keep working when a slug is added here.
+const SLUG_ALIASES: &[(&str, &str, &str)] = &[
+    // 0.6.0 guides were linked by their file names.
+    ("0.6.0", "fetch", "http-requests-and-state"),
+    ("0.6.0", "misc", "misc-features"),
+    ("0.6.0", "js", "javascript-interaction"),
+];
+
+/// The current slug of the guide, `slug` itself if it isn't an alias.
+pub fn current_slug<'a>(seed_version: &str, slug: &'a str) -> &'a str {
+    SLUG_ALIASES
+        .iter()
+        .find(|(alias_version, old_slug, _)| {
+            *alias_version == seed_version && *old_slug == slug
+        })
+        .map_or(slug, |(_, _, new_slug)| *new_slug)
+}
+
+/// Heading ids were generated with runs of `-` before, they're collapsed now
+/// (e.g. `todomvc---model` => `todomvc-model`).
+pub fn current_heading_id(heading_id: &str) -> String {
+    heading_id
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Finds the guide by its current slug or by an alias.
+pub fn find<'a>(
+    guides: &'a [Guide],
+    seed_version: &str,
+    slug: &str,
+) -> Option<&'a Guide> {
+    let slug = current_slug(seed_version, slug);
+    guides
+        .iter()
+        .find(|guide| guide.seed_version == seed_version && guide.slug == slug)
+}
+
+/// The guide with the same slug in the newest Seed version that has it.
+pub fn latest_equivalent<'a>(
+    guides: &'a [Guide],
+    guide: &'a Guide,
+) -> &'a Guide {
+    SEED_VERSIONS
+        .iter()
+        .find_map(|seed_version| {
+            find(guides, seed_version.version(), guide.slug)
+        })
+        .unwrap_or(guide)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Heading {
+    pub level: u8,
+    pub id: &'static str,
+    pub title: &'static str,
+}
+
 macro_rules! guide {
     (seed_version: $seed_version:literal, slug: $slug:literal, menu_title: $menu_title:literal, file_name: $file_name:literal, prepend_menu_divider: $prepend_menu_divider:literal) => {
         Guide {
@@ -23,6 +106,10 @@ macro_rules! guide {
             menu_title: $menu_title,
             html: include_str!(concat!("../generated_guides/", concat!($seed_version, "/", $file_name, ".html"))),
             lowercase_text: include_str!(concat!("../generated_guides/", concat!($seed_version, "/", $file_name, ".txt"))),
+            text: include_str!(concat!("../generated_guides/", concat!($seed_version, "/", $file_name, ".plain.txt"))),
+            outline: include_str!(concat!("../generated_guides/", concat!($seed_version, "/", $file_name, ".outline.txt"))),
+            description: include_str!(concat!("../generated_guides/", concat!($seed_version, "/", $file_name, ".description.txt"))),
+            og_image_path: concat!("/og/", concat!($seed_version, "/", $file_name, ".png")),
             prepend_menu_divider: $prepend_menu_divider,
             edit_url: concat!("https://github.com/seed-rs/seed-rs.org/tree/master/crate/guides/", concat!($seed_version, "/", $file_name, ".md")),
         }
@@ -9,83 +9,217 @@
     clippy::wildcard_imports
 )]
 
+mod annotations;
+mod bookmarks;
+mod changelog;
+mod code_block;
+mod config;
 mod generated;
+mod glossary;
 mod guide;
+mod offline;
 mod page;
+pub mod prerender;
+mod reading_progress;
+mod search;
+mod seo;
+mod shortcut;
+pub mod sitemap;
+mod storage;
 
+use annotations::{Annotations, TextSelection};
+use bookmarks::Bookmarks;
+use config::{Config, Settings};
 use generated::css_classes::C;
 use guide::Guide;
-use page::partial::blender;
+use offline::OfflineStatus;
+use page::partial::{
+    annotation_editor, bookmarks_panel, command_palette, shortcut_help,
+    update_prompt,
+};
+use reading_progress::ReadingProgress;
 use seed::{prelude::*, *};
 use serde::{Deserialize, Serialize};
+use shortcut::Shortcuts;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 
 use Visibility::{Hidden, Visible};
 
+const SITE_URL: &str = "https://seed-rs.org";
 const TITLE_SUFFIX: &str = "Seed";
-const STORAGE_KEY: &str = "seed";
-const USER_AGENT_FOR_PRERENDERING: &str = "ReactSnap";
 const DEFAULT_GUIDE_SLUG: &str = "about";
+const SEARCH_PATH_PART: &str = "search";
+const SETTINGS_PATH_PART: &str = "settings";
+const ALL_GUIDES_PATH_PART: &str = "all";
+const CHANGELOG_PATH_PART: &str = "changelog";
+const GLOSSARY_PATH_PART: &str = "glossary";
+/// EPUBs generated by `build.rs` are served from `/epub/`.
+const EPUB_PATH_PART: &str = "epub";
+/// The Atom feed of releases generated by `build.rs`.
+const CHANGELOG_FEED_PATH_PART: &str = "changelog.atom";
+const SEARCH_QUERY_PARAM: &str = "q";
+const SEARCH_VERSION_PARAM: &str = "version";
+const SEARCH_PAGE_PARAM: &str = "page";
+const HIGHLIGHT_PARAM: &str = "highlight";
+const CHANGELOG_FROM_PARAM: &str = "from";
+const CHANGELOG_TO_PARAM: &str = "to";
+const CHANGELOG_KIND_PARAM: &str = "kind";
 const SEED_VERSIONS: &[SeedVersion] =
     &[SeedVersion::V0_8_0, SeedVersion::V0_7_0, SeedVersion::V0_6_0];
 const DEFAULT_SEED_VERSION: SeedVersion = SeedVersion::V0_8_0;
-const LEFT_ARROW_KEY: &str = "ArrowLeft";
-const RIGHT_ARROW_KEY: &str = "ArrowRight";
+const DARK_COLOR_SCHEME_QUERY: &str = "(prefers-color-scheme: dark)";
 
 // ------ ------
 //     Init
 // ------ ------
 
 fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.subscribe(Msg::UrlChanged).stream(streams::window_event(
-        Ev::KeyDown,
-        |event| {
-            let tag = event
-                .target()
-                .unwrap()
-                .unchecked_into::<web_sys::Element>()
-                .tag_name();
-            if tag == "INPUT" {
-                // Ignore `input` elements.
-                // Rewrite to `prevent_default` / `stop_propagation once cumbersome.
-                None
-            } else {
-                let keyboard_event =
-                    event.unchecked_into::<web_sys::KeyboardEvent>();
-                match keyboard_event.key().as_str() {
-                    LEFT_ARROW_KEY => Some(Msg::GoToPreviousGuide),
-                    RIGHT_ARROW_KEY => Some(Msg::GoToNextGuide),
-                    _ => None,
-                }
+    orders
+        .subscribe(Msg::UrlChanged)
+        // Let the browser download EPUBs and the feed
+        // instead of routing to them.
+        .subscribe(|subs::UrlRequested(url, url_request)| {
+            let first_part = url.path().first().map(String::as_str);
+            if first_part == Some(EPUB_PATH_PART)
+                || first_part == Some(CHANGELOG_FEED_PATH_PART)
+            {
+                url_request.handled();
             }
-        },
-    ));
+        })
+        .stream(streams::window_event(Ev::KeyDown, |event| {
+            Msg::KeyDown(event.unchecked_into())
+        }))
+        .stream(streams::window_event(Ev::Scroll, |_| {
+            Msg::CheckReadingCompletion
+        }))
+        // Fired when another tab changes the local storage.
+        .stream(streams::window_event(Ev::from("storage"), |event| {
+            Msg::StorageChanged(
+                event.unchecked_into::<web_sys::StorageEvent>().key(),
+            )
+        }));
+
+    let config = config::load();
+    let system_prefers_dark = subscribe_to_color_scheme(orders);
+    set_theme(config.mode.theme(system_prefers_dark));
+    set_reduced_motion(config.settings.reduced_motion);
+    if let Some(heading_id) = url.hash().cloned() {
+        orders.after_next_render(|_| Msg::ScrollToHeading(heading_id));
+    }
 
-    let guides = guide::guides();
-    let mut selected_seed_version = DEFAULT_SEED_VERSION;
+    let mut model = Model::new(url, config, system_prefers_dark);
+    // The page may be served from another prerendered page.
+    seo::update_head(model.page, &model.guides);
+    if model.guide_highlight.is_some() {
+        orders.after_next_render(|_| Msg::ScrollToSearchMatch);
+    }
+    offline::set_manifest(&model.guides, &model.base_url);
+    if offline::register_service_worker(orders) {
+        model.offline_status = OfflineStatus::Caching {
+            cached: 0,
+            total: 0,
+        };
+    }
+    model.reading_progress = ReadingProgress::load();
+    if let Page::Guide {
+        guide,
+        ..
+    } = model.page
+    {
+        model.reading_progress.visit(&guide);
+        model.reading_progress.save();
+        orders.after_next_render(|_| Msg::CheckReadingCompletion);
+    }
+    model.bookmarks = Bookmarks::load();
+    model.annotations = Annotations::load();
+    model
+}
+
+/// Returns `true` if the OS prefers dark colors
+/// and sends `Msg::SystemColorSchemeChanged` when the preference changes.
+fn subscribe_to_color_scheme(orders: &mut impl Orders<Msg>) -> bool {
+    let media_query_list = match window().match_media(DARK_COLOR_SCHEME_QUERY)
+    {
+        Ok(Some(media_query_list)) => media_query_list,
+        // Browsers without `matchMedia` support.
+        _ => return false,
+    };
+    let msg_sender = orders.msg_sender();
+    let changed_list = media_query_list.clone();
+    let on_change = Closure::wrap(Box::new(move |_| {
+        msg_sender(Some(Msg::SystemColorSchemeChanged(
+            changed_list.matches(),
+        )));
+    }) as Box<dyn Fn(web_sys::Event)>);
+    media_query_list
+        .add_event_listener_with_callback(
+            "change",
+            on_change.as_ref().unchecked_ref(),
+        )
+        .expect("add color scheme listener");
+    // The listener lives as long as the app.
+    on_change.forget();
+    media_query_list.matches()
+}
+
+/// Applies a config changed in another tab.
+fn apply_config(config: Config, model: &mut Model) {
+    model.mode = config.mode;
+    model.settings = config.settings;
+    model.shortcuts = Shortcuts::new(config.shortcut_overrides);
+    set_theme(model.theme());
+    set_reduced_motion(model.settings.reduced_motion);
+}
+
+fn focus_annotation_note_input(model: &Model, orders: &mut impl Orders<Msg>) {
+    let note_input = model.annotation_note_input.clone();
+    orders.after_next_render(move |_| {
+        if let Some(note_input) = note_input.get() {
+            note_input.focus().expect("focus annotation note input");
+        }
+    });
+}
 
-    Model {
-        base_url: url.to_base_url(),
-        page: Page::init(url, &guides, &mut selected_seed_version),
-        selected_seed_version,
-        guide_list_visibility: Hidden,
-        menu_visibility: Hidden,
-        in_prerendering: is_in_prerendering(),
-        guides,
-        search_query: String::new(),
-        matched_guides: Vec::new(),
-        mode: load_config().mode,
-        guide_content_el: ElRef::new(),
+fn precache_offline_guides(model: &Model, orders: &mut impl Orders<Msg>) {
+    if model.offline_status != OfflineStatus::Unsupported {
+        offline::precache_guides(
+            &model.guides,
+            &model.settings.offline_versions,
+            &model.base_url,
+            orders,
+        );
     }
 }
 
-fn load_config() -> Config {
-    LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+fn save_config(model: &Model) {
+    config::save(&Config::new(
+        model.mode,
+        model.settings.clone(),
+        model.shortcuts.overrides().clone(),
+    ));
 }
 
-fn is_in_prerendering() -> bool {
-    let user_agent = window().navigator().user_agent().expect("get user agent");
-    user_agent == USER_AGENT_FOR_PRERENDERING
+fn url_search_value<'a>(url: &'a Url, key: &str) -> Option<&'a str> {
+    url.search()
+        .get(key)
+        .and_then(|values| values.first())
+        .map(String::as_str)
+}
+
+/// Results are computed only for the search page.
+fn search_results(
+    page: Page,
+    guides: &[Guide],
+    query: &str,
+) -> Vec<search::SearchResult> {
+    match page {
+        Page::Search {
+            seed_version,
+            ..
+        } => search::ranked_results(guides, query, seed_version),
+        _ => Vec::new(),
+    }
 }
 
 // ------ ------
@@ -98,12 +232,88 @@ pub struct Model {
     pub selected_seed_version: SeedVersion,
     pub guide_list_visibility: Visibility,
     pub menu_visibility: Visibility,
-    pub in_prerendering: bool,
     pub guides: Vec<Guide>,
     pub search_query: String,
-    pub matched_guides: Vec<Guide>,
+    pub matched_guides: Vec<search::GuideMatch>,
+    /// Ranked results of the search page.
+    pub search_results: Vec<search::SearchResult>,
+    pub search_in_all_versions: bool,
+    pub guide_highlight: Option<GuideHighlight>,
     pub mode: Mode,
+    pub system_prefers_dark: bool,
+    pub settings: Settings,
     pub guide_content_el: ElRef<web_sys::HtmlElement>,
+    pub command_palette: Option<CommandPalette>,
+    pub command_palette_input: ElRef<web_sys::HtmlInputElement>,
+    pub search_input: ElRef<web_sys::HtmlInputElement>,
+    pub shortcuts: Shortcuts,
+    pub shortcut_help: Option<ShortcutHelp>,
+    pub reading_progress: ReadingProgress,
+    pub bookmarks: Bookmarks,
+    pub bookmarks_panel: Option<BookmarksPanel>,
+    pub annotations: Annotations,
+    pub text_selection: Option<TextSelection>,
+    pub annotation_editor: Option<AnnotationEditor>,
+    pub annotation_note_input: ElRef<web_sys::HtmlTextAreaElement>,
+    pub offline_status: OfflineStatus,
+    /// A new deployment is waiting for the page reload.
+    pub update_available: bool,
+}
+
+impl Model {
+    /// The model without browser state (stored data, service worker, etc.).
+    /// It's also used by the prerenderer.
+    pub fn new(url: Url, config: Config, system_prefers_dark: bool) -> Self {
+        let guides = guide::guides();
+        let mut selected_seed_version = DEFAULT_SEED_VERSION;
+        let base_url = url.to_base_url();
+        let search_query =
+            url_search_value(&url, SEARCH_QUERY_PARAM).map(ToOwned::to_owned);
+        let highlight_query =
+            url_search_value(&url, HIGHLIGHT_PARAM).map(ToOwned::to_owned);
+        let page = Page::init(url, &guides, &mut selected_seed_version);
+        let guide_highlight = GuideHighlight::new(page, highlight_query);
+        let search_query = match page {
+            Page::Search { .. } => search_query.unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        Self {
+            base_url,
+            page,
+            selected_seed_version,
+            guide_list_visibility: Hidden,
+            menu_visibility: Hidden,
+            matched_guides: search::search(&guides, &search_query),
+            search_results: search_results(page, &guides, &search_query),
+            guides,
+            search_query,
+            search_in_all_versions: false,
+            guide_highlight,
+            mode: config.mode,
+            system_prefers_dark,
+            settings: config.settings,
+            guide_content_el: ElRef::new(),
+            command_palette: None,
+            command_palette_input: ElRef::new(),
+            search_input: ElRef::new(),
+            shortcuts: Shortcuts::new(config.shortcut_overrides),
+            shortcut_help: None,
+            reading_progress: ReadingProgress::default(),
+            bookmarks: Bookmarks::default(),
+            bookmarks_panel: None,
+            annotations: Annotations::default(),
+            text_selection: None,
+            annotation_editor: None,
+            annotation_note_input: ElRef::new(),
+            offline_status: OfflineStatus::Unsupported,
+            update_available: false,
+        }
+    }
+
+    pub const fn theme(&self) -> Theme {
+        self.mode.theme(self.system_prefers_dark)
+    }
 }
 
 // ------ SeedVersion ------
@@ -131,6 +341,78 @@ impl SeedVersion {
             Self::V0_6_0 => "Feb 1, 2020",
         }
     }
+
+    pub fn epub_path(self) -> String {
+        format!("/{}/seed-{}.epub", EPUB_PATH_PART, self.version())
+    }
+
+    pub fn from_version(version: &str) -> Option<Self> {
+        SEED_VERSIONS
+            .iter()
+            .find(|seed_version| seed_version.version() == version)
+            .copied()
+    }
+}
+
+// ------ GuideHighlight ------
+
+/// Occurrences of the search query marked in the opened guide.
+pub struct GuideHighlight {
+    pub query: String,
+    pub active_match: usize,
+    pub match_count: usize,
+}
+
+impl GuideHighlight {
+    fn new(page: Page, query: Option<String>) -> Option<Self> {
+        let query = query.filter(|query| !query.trim().is_empty())?;
+        if let Page::Guide {
+            guide,
+            ..
+        } = page
+        {
+            Some(Self {
+                match_count: search::count_matches_in_html(guide.html, &query),
+                query,
+                active_match: 0,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+// ------ CommandPalette ------
+
+#[derive(Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub selected_index: usize,
+}
+
+// ------ ShortcutHelp ------
+
+#[derive(Default)]
+pub struct ShortcutHelp {
+    /// The action whose new binding couldn't be parsed.
+    pub invalid_binding: Option<shortcut::Action>,
+}
+
+// ------ BookmarksPanel ------
+
+#[derive(Default)]
+pub struct BookmarksPanel {
+    /// The number of imported bookmarks or an import error.
+    pub import_result: Option<Result<usize, String>>,
+}
+
+// ------ AnnotationEditor ------
+
+pub struct AnnotationEditor {
+    pub index: usize,
+    pub note: String,
+    /// The annotation is removed on cancel.
+    pub is_new: bool,
 }
 
 // ------ Visibility  ------
@@ -150,36 +432,114 @@ impl Visibility {
     }
 }
 
-// ------ Config ------
-
-#[derive(Default, Serialize, Deserialize)]
-pub struct Config {
-    mode: Mode,
-}
-
 // ------ Mode  ------
 
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Mode {
+    /// Follows `prefers-color-scheme`.
+    System,
     Light,
     Dark,
+    HighContrast,
 }
 
 impl Mode {
+    pub const ALL: &'static [Self] =
+        &[Self::System, Self::Light, Self::Dark, Self::HighContrast];
+
     pub fn toggle(&mut self) {
-        *self = match self {
+        *self = self.next();
+    }
+
+    pub const fn next(self) -> Self {
+        match self {
+            Self::System => Self::Light,
             Self::Light => Self::Dark,
-            Self::Dark => Self::Light,
+            Self::Dark => Self::HighContrast,
+            Self::HighContrast => Self::System,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::System => "System",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    pub const fn theme(self, system_prefers_dark: bool) -> Theme {
+        match self {
+            Self::System if system_prefers_dark => Theme::Dark,
+            Self::System | Self::Light => Theme::Light,
+            Self::Dark => Theme::Dark,
+            Self::HighContrast => Theme::HighContrast,
         }
     }
 }
 
 impl Default for Mode {
     fn default() -> Self {
-        Self::Light
+        Self::System
     }
 }
 
+// ------ Theme  ------
+
+/// Colors actually used - `Mode` with resolved `Mode::System`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl Theme {
+    const ALL: &'static [Self] = &[Self::Light, Self::Dark, Self::HighContrast];
+
+    /// `true` if the theme has light text on a dark background.
+    pub const fn is_dark(self) -> bool {
+        match self {
+            Self::Light => false,
+            Self::Dark | Self::HighContrast => true,
+        }
+    }
+
+    /// Class with theme color tokens. (see `css/themes.css`)
+    fn class(self) -> &'static str {
+        match self {
+            Self::Light => C.theme_light,
+            Self::Dark => C.theme_dark,
+            Self::HighContrast => C.theme_high_contrast,
+        }
+    }
+}
+
+/// Toggles the class disabling animations on the `html` element.
+fn set_reduced_motion(reduced_motion: bool) {
+    document()
+        .document_element()
+        .expect("get document element")
+        .class_list()
+        .toggle_with_force(C.reduced_motion, reduced_motion)
+        .expect("toggle reduced motion class");
+}
+
+/// Replaces the theme class on the `html` element.
+fn set_theme(theme: Theme) {
+    let class_list = document()
+        .document_element()
+        .expect("get document element")
+        .class_list();
+    for other_theme in Theme::ALL {
+        class_list
+            .remove_1(other_theme.class())
+            .expect("remove theme class");
+    }
+    class_list.add_1(theme.class()).expect("add theme class");
+}
+
 // ------ Page ------
 
 #[derive(Copy, Clone, PartialEq)]
@@ -188,10 +548,75 @@ pub enum Page {
         guide: Guide,
         show_intro: bool,
     },
+    Search {
+        seed_version: Option<SeedVersion>,
+        page_number: usize,
+    },
+    Settings,
+    AllGuides {
+        seed_version: SeedVersion,
+    },
+    Changelog {
+        filter: changelog::Filter,
+    },
+    Glossary,
     NotFound,
 }
 
 impl Page {
+    pub fn title(self) -> String {
+        match self {
+            Self::Guide {
+                guide,
+                ..
+            } => format!("{} - {}", guide.menu_title, TITLE_SUFFIX),
+            Self::Search { .. } => format!("Search - {}", TITLE_SUFFIX),
+            Self::Settings => format!("Settings - {}", TITLE_SUFFIX),
+            Self::AllGuides {
+                seed_version,
+            } => format!(
+                "All guides {} - {}",
+                seed_version.version(),
+                TITLE_SUFFIX
+            ),
+            Self::Changelog { .. } => format!("Changelog - {}", TITLE_SUFFIX),
+            Self::Glossary => format!("Glossary - {}", TITLE_SUFFIX),
+            Self::NotFound => format!("404 - {}", TITLE_SUFFIX),
+        }
+    }
+
+    /// Absolute URL for search engines. Guides point to the same guide
+    /// in the newest Seed version, so old versions don't compete with it.
+    pub fn canonical_url(self, guides: &[Guide]) -> Option<String> {
+        match self {
+            Self::Guide {
+                show_intro: true,
+                ..
+            } => Some(format!("{}/", SITE_URL)),
+            Self::Guide {
+                guide,
+                ..
+            } => {
+                let guide = guide::latest_equivalent(guides, &guide);
+                let url = Urls::new(Url::new()).guide(guide);
+                Some(format!("{}{}", SITE_URL, prerender::href(&url)))
+            },
+            Self::Changelog { .. } => {
+                let url = Urls::new(Url::new())
+                    .changelog(changelog::Filter::default());
+                Some(format!("{}{}", SITE_URL, prerender::href(&url)))
+            },
+            Self::Glossary => {
+                let url = Urls::new(Url::new()).glossary();
+                Some(format!("{}{}", SITE_URL, prerender::href(&url)))
+            },
+            Self::Search { .. }
+            | Self::Settings
+            | Self::AllGuides { .. }
+            | Self::NotFound => None,
+        }
+    }
+
     #[allow(clippy::option_if_let_else)]
     /// # Panics
     ///
@@ -201,6 +626,21 @@ impl Page {
         guides: &[Guide],
         selected_seed_version: &mut SeedVersion,
     ) -> Self {
+        let search_seed_version = url_search_value(&url, SEARCH_VERSION_PARAM)
+            .and_then(SeedVersion::from_version);
+        let search_page_number = url_search_value(&url, SEARCH_PAGE_PARAM)
+            .and_then(|page_number| page_number.parse().ok())
+            .filter(|page_number| *page_number > 0)
+            .unwrap_or(1);
+        let changelog_filter = changelog::Filter {
+            from: url_search_value(&url, CHANGELOG_FROM_PARAM)
+                .and_then(changelog::Version::parse),
+            to: url_search_value(&url, CHANGELOG_TO_PARAM)
+                .and_then(changelog::Version::parse),
+            kind: url_search_value(&url, CHANGELOG_KIND_PARAM)
+                .and_then(changelog::EntryKind::from_id),
+        };
+
         match url.remaining_path_parts().as_slice() {
             [] => {
                 if let Some(guide) = guides.iter().find(|guide| {
@@ -216,15 +656,38 @@ impl Page {
                     Self::NotFound
                 }
             },
+            [SEARCH_PATH_PART] => {
+                if let Some(seed_version) = search_seed_version {
+                    *selected_seed_version = seed_version;
+                }
+                Self::Search {
+                    seed_version: search_seed_version,
+                    page_number: search_page_number,
+                }
+            },
+            [SETTINGS_PATH_PART] => Self::Settings,
+            [CHANGELOG_PATH_PART] => Self::Changelog {
+                filter: changelog_filter,
+            },
+            [GLOSSARY_PATH_PART] => Self::Glossary,
+            [seed_version, ALL_GUIDES_PATH_PART] => {
+                if let Some(seed_version) =
+                    SeedVersion::from_version(seed_version)
+                {
+                    *selected_seed_version = seed_version;
+                    Self::AllGuides {
+                        seed_version,
+                    }
+                } else {
+                    Self::NotFound
+                }
+            },
             [seed_version, guide_slug] => {
-                if let Some(guide) = guides.iter().find(|guide| {
-                    guide.slug == *guide_slug
-                        && guide.seed_version == *seed_version
-                }) {
-                    *selected_seed_version = *SEED_VERSIONS
-                        .iter()
-                        .find(|version| version.version() == guide.seed_version)
-                        .unwrap();
+                if let Some(guide) =
+                    guide::find(guides, seed_version, guide_slug)
+                {
+                    *selected_seed_version =
+                        SeedVersion::from_version(guide.seed_version).unwrap();
                     Self::Guide {
                         guide: *guide,
                         show_intro: guide.slug == DEFAULT_GUIDE_SLUG,
@@ -253,6 +716,80 @@ impl<'a> Urls<'a> {
             .add_path_part(guide.seed_version)
             .add_path_part(guide.slug)
     }
+
+    pub fn heading(self, guide: &Guide, heading_id: &str) -> Url {
+        self.guide(guide).set_hash(heading_id)
+    }
+
+    pub fn guide_with_highlight(self, guide: &Guide, query: &str) -> Url {
+        let mut url = self.guide(guide);
+        if !query.is_empty() {
+            url.search_mut()
+                .insert(HIGHLIGHT_PARAM.to_owned(), vec![query.to_owned()]);
+        }
+        url
+    }
+
+    pub fn settings(self) -> Url {
+        self.base_url().add_path_part(SETTINGS_PATH_PART)
+    }
+
+    pub fn all_guides(self, seed_version: SeedVersion) -> Url {
+        self.base_url()
+            .add_path_part(seed_version.version())
+            .add_path_part(ALL_GUIDES_PATH_PART)
+    }
+
+    pub fn changelog(self, filter: changelog::Filter) -> Url {
+        let mut url = self.base_url().add_path_part(CHANGELOG_PATH_PART);
+        let search = url.search_mut();
+        let params = vec![
+            (CHANGELOG_FROM_PARAM, filter.from.map(|from| from.to_string())),
+            (CHANGELOG_TO_PARAM, filter.to.map(|to| to.to_string())),
+            (
+                CHANGELOG_KIND_PARAM,
+                filter.kind.map(|kind| kind.id().to_owned()),
+            ),
+        ];
+        for (key, value) in params {
+            if let Some(value) = value {
+                search.insert(key.to_owned(), vec![value]);
+            }
+        }
+        url
+    }
+
+    pub fn glossary(self) -> Url {
+        self.base_url().add_path_part(GLOSSARY_PATH_PART)
+    }
+
+    pub fn search_base(self) -> Url {
+        self.base_url().add_path_part(SEARCH_PATH_PART)
+    }
+
+    pub fn search(
+        self,
+        query: &str,
+        seed_version: Option<SeedVersion>,
+        page_number: usize,
+    ) -> Url {
+        let mut url = self.search_base();
+        let search = url.search_mut();
+        search.insert(SEARCH_QUERY_PARAM.to_owned(), vec![query.to_owned()]);
+        if let Some(seed_version) = seed_version {
+            search.insert(
+                SEARCH_VERSION_PARAM.to_owned(),
+                vec![seed_version.version().to_owned()],
+            );
+        }
+        if page_number > 1 {
+            search.insert(
+                SEARCH_PAGE_PARAM.to_owned(),
+                vec![page_number.to_string()],
+            );
+        }
+        url
+    }
 }
 
 // ------ ------
@@ -267,31 +804,112 @@ pub enum Msg {
     ToggleMenu,
     HideMenu,
     SearchQueryChanged(String),
+    SubmitSearch,
+    ToggleSearchInAllVersions,
+    PreviousSearchMatch,
+    NextSearchMatch,
+    ScrollToSearchMatch,
+    ScrollToCodeBlockSearchMatch,
+    ClearSearchHighlight,
     ToggleMode,
+    SelectMode(Mode),
+    SystemColorSchemeChanged(bool),
+    SelectFontSize(config::FontSize),
+    SelectContentWidth(config::ContentWidth),
+    SelectCodeTheme(config::CodeTheme),
+    ToggleReducedMotion,
+    ResetSettings,
+    StorageChanged(Option<String>),
+    CheckReadingCompletion,
+    ToggleGuideCompleted,
+    ToggleGuideBookmark,
+    ToggleHeadingBookmark(String),
+    ToggleBookmarksPanel,
+    RemoveBookmark(usize),
+    ImportBookmarks(web_sys::File),
+    BookmarksFileLoaded(Option<String>),
+    TextSelectionChanged(Option<TextSelection>),
+    HighlightSelection,
+    AnnotateSelection,
+    EditAnnotation(usize),
+    AnnotationNoteChanged(String),
+    SaveAnnotationNote,
+    CancelAnnotationEdit,
+    RemoveAnnotation(usize),
+    ServiceWorkerRegistered { update_waiting: bool },
+    OfflineStatusChanged(OfflineStatus),
+    ToggleOfflineVersion(SeedVersion),
+    UpdateAvailable,
+    ReloadToUpdate,
+    DismissUpdate,
     SwitchVersion(SeedVersion),
     GoToPreviousGuide,
     GoToNextGuide,
+    GoToUrl(Url),
+    ScrollToHeading(String),
+    ToggleCommandPalette,
+    CloseCommandPalette,
+    CommandPaletteQueryChanged(String),
+    SelectPreviousCommand(usize),
+    SelectNextCommand(usize),
+    ExecuteCommand(usize),
+    ExecuteSelectedCommand,
+    KeyDown(web_sys::KeyboardEvent),
+    FocusSearch,
+    ToggleShortcutHelp,
+    CloseOverlays,
+    ChangeShortcut(shortcut::Action, String),
+    ResetShortcut(shortcut::Action),
 }
 
 pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     match msg {
         Msg::UrlChanged(subs::UrlChanged(url)) => {
+            let search_query = url_search_value(&url, SEARCH_QUERY_PARAM)
+                .map(ToOwned::to_owned);
+            let highlight_query =
+                url_search_value(&url, HIGHLIGHT_PARAM).map(ToOwned::to_owned);
+            let heading_id = url.hash().cloned();
             model.page = Page::init(
                 url,
                 &model.guides,
                 &mut model.selected_seed_version,
             );
+            model.text_selection = None;
+            model.guide_highlight =
+                GuideHighlight::new(model.page, highlight_query);
 
-            let title = match model.page {
-                Page::Guide {
-                    guide,
-                    ..
-                } => format!("{} - {}", guide.menu_title, TITLE_SUFFIX),
-                Page::NotFound => format!("404 - {}", TITLE_SUFFIX),
-            };
-            document().set_title(&title);
+            seo::update_head(model.page, &model.guides);
+
+            if let Page::Search { .. } = model.page {
+                let query = search_query.unwrap_or_default();
+                model.matched_guides = search::search(&model.guides, &query);
+                model.search_query = query;
+            }
+            model.search_results =
+                search_results(model.page, &model.guides, &model.search_query);
+
+            if let Page::Guide {
+                guide,
+                ..
+            } = model.page
+            {
+                model.reading_progress.visit(&guide);
+                model.reading_progress.save();
+                orders.after_next_render(|_| Msg::CheckReadingCompletion);
+            }
 
             orders.send_msg(Msg::ScrollToTop);
+            if let Some(heading_id) = heading_id {
+                orders.after_next_render(|_| Msg::ScrollToHeading(heading_id));
+            } else if model.guide_highlight.is_some() {
+                orders.after_next_render(|_| Msg::ScrollToSearchMatch);
+            }
+        },
+        Msg::ScrollToHeading(heading_id) => {
+            if let Some(heading) = document().get_element_by_id(&heading_id) {
+                heading.scroll_into_view();
+            }
         },
         Msg::ScrollToTop => {
             // scroll on mobile + tablet
@@ -314,18 +932,334 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             model.menu_visibility = Hidden;
         },
         Msg::SearchQueryChanged(query) => {
-            model.matched_guides = search(&model.guides, &query);
+            model.matched_guides = search::search(&model.guides, &query);
             model.search_query = query;
+
+            // Keep the URL of the search page in sync with the search input.
+            if let Page::Search {
+                seed_version,
+                page_number,
+            } = &mut model.page
+            {
+                *page_number = 1;
+                Urls::new(&model.base_url)
+                    .search(&model.search_query, *seed_version, 1)
+                    .go_and_replace();
+            }
+            model.search_results =
+                search_results(model.page, &model.guides, &model.search_query);
+        },
+        Msg::SubmitSearch => {
+            let seed_version = match model.page {
+                Page::Search {
+                    seed_version,
+                    ..
+                } => seed_version,
+                _ => None,
+            };
+            model.guide_list_visibility = Hidden;
+            orders.notify(subs::UrlRequested::new(
+                Urls::new(&model.base_url).search(
+                    &model.search_query,
+                    seed_version,
+                    1,
+                ),
+            ));
+        },
+        Msg::ToggleSearchInAllVersions => {
+            model.search_in_all_versions = !model.search_in_all_versions;
+        },
+        Msg::PreviousSearchMatch => {
+            if let Some(highlight) = &mut model.guide_highlight {
+                if highlight.match_count > 0 {
+                    highlight.active_match = highlight
+                        .active_match
+                        .checked_sub(1)
+                        .unwrap_or(highlight.match_count - 1);
+                    orders.after_next_render(|_| Msg::ScrollToSearchMatch);
+                }
+            }
+        },
+        Msg::NextSearchMatch => {
+            if let Some(highlight) = &mut model.guide_highlight {
+                if highlight.match_count > 0 {
+                    highlight.active_match =
+                        (highlight.active_match + 1) % highlight.match_count;
+                    orders.after_next_render(|_| Msg::ScrollToSearchMatch);
+                }
+            }
+        },
+        Msg::ScrollToSearchMatch => {
+            if !scroll_to_search_match(model) {
+                // `<code-block>`s mark their matches after they're updated.
+                orders.perform_cmd(cmds::timeout(0, || {
+                    Msg::ScrollToCodeBlockSearchMatch
+                }));
+            }
+        },
+        Msg::ScrollToCodeBlockSearchMatch => {
+            scroll_to_search_match(model);
+        },
+        Msg::ClearSearchHighlight => {
+            model.guide_highlight = None;
+            if let Page::Guide {
+                guide,
+                ..
+            } = &model.page
+            {
+                Urls::new(&model.base_url).guide(guide).go_and_replace();
+            }
         },
         Msg::ToggleMode => {
             model.mode.toggle();
-
-            let config = Config {
-                mode: model.mode,
+            set_theme(model.theme());
+            save_config(model);
+        },
+        Msg::SelectMode(mode) => {
+            model.mode = mode;
+            set_theme(model.theme());
+            save_config(model);
+        },
+        Msg::SystemColorSchemeChanged(prefers_dark) => {
+            model.system_prefers_dark = prefers_dark;
+            set_theme(model.theme());
+        },
+        Msg::SelectFontSize(font_size) => {
+            model.settings.font_size = font_size;
+            save_config(model);
+        },
+        Msg::SelectContentWidth(content_width) => {
+            model.settings.content_width = content_width;
+            save_config(model);
+        },
+        Msg::SelectCodeTheme(code_theme) => {
+            model.settings.code_theme = code_theme;
+            save_config(model);
+        },
+        Msg::ToggleReducedMotion => {
+            model.settings.reduced_motion = !model.settings.reduced_motion;
+            set_reduced_motion(model.settings.reduced_motion);
+            save_config(model);
+        },
+        Msg::ResetSettings => {
+            model.settings = Settings::default();
+            set_reduced_motion(model.settings.reduced_motion);
+            save_config(model);
+            precache_offline_guides(model, orders);
+        },
+        Msg::StorageChanged(key) => {
+            // `None` means the whole storage has been cleared.
+            let changed = |storage_key| {
+                key.as_deref().map_or(true, |key| key == storage_key)
             };
-            LocalStorage::insert(STORAGE_KEY, &config)
-                .expect("insert to local storage");
+            if changed(config::STORAGE_KEY) {
+                apply_config(config::load(), model);
+            }
+            if changed(reading_progress::STORAGE_KEY) {
+                model.reading_progress = ReadingProgress::load();
+            }
+            if changed(bookmarks::STORAGE_KEY) {
+                model.bookmarks = Bookmarks::load();
+            }
+            if changed(annotations::STORAGE_KEY) {
+                let annotations = Annotations::load();
+                // Indices may point to other annotations now.
+                model.annotation_editor =
+                    model.annotation_editor.take().and_then(|editor| {
+                        let index = model
+                            .annotations
+                            .get(editor.index)
+                            .and_then(|edited| annotations.position(edited))?;
+                        Some(AnnotationEditor {
+                            index,
+                            ..editor
+                        })
+                    });
+                model.annotations = annotations;
+            }
+        },
+        Msg::CheckReadingCompletion => match model.page {
+            Page::Guide {
+                guide,
+                ..
+            } if !model.reading_progress.is_completed(&guide)
+                && reading_progress::is_scrolled_to_end() =>
+            {
+                model.reading_progress.complete(&guide);
+                model.reading_progress.save();
+            },
+            // Don't rerender on every scroll event.
+            _ => {
+                orders.skip();
+            },
+        },
+        Msg::ToggleGuideCompleted => {
+            if let Page::Guide {
+                guide,
+                ..
+            } = model.page
+            {
+                if model.reading_progress.is_completed(&guide) {
+                    model.reading_progress.uncomplete(&guide);
+                } else {
+                    model.reading_progress.complete(&guide);
+                }
+                model.reading_progress.save();
+            }
+        },
+        Msg::ToggleGuideBookmark => {
+            if let Page::Guide {
+                guide,
+                ..
+            } = model.page
+            {
+                model.bookmarks.toggle(&guide, None, &model.guides);
+                model.bookmarks.save();
+            }
+        },
+        Msg::ToggleHeadingBookmark(heading_id) => {
+            if let Page::Guide {
+                guide,
+                ..
+            } = model.page
+            {
+                if let Some(heading) =
+                    guide.headings().find(|heading| heading.id == heading_id)
+                {
+                    model.bookmarks.toggle(
+                        &guide,
+                        Some(heading),
+                        &model.guides,
+                    );
+                    model.bookmarks.save();
+                }
+            }
         },
+        Msg::ToggleBookmarksPanel => {
+            model.bookmarks_panel = match model.bookmarks_panel {
+                Some(_) => None,
+                None => Some(BookmarksPanel::default()),
+            };
+        },
+        Msg::RemoveBookmark(index) => {
+            model.bookmarks.remove(index);
+            model.bookmarks.save();
+        },
+        Msg::ImportBookmarks(file) => {
+            orders.perform_cmd(async move {
+                let text = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|text| text.as_string());
+                Msg::BookmarksFileLoaded(text)
+            });
+        },
+        Msg::BookmarksFileLoaded(text) => {
+            let import_result = text
+                .ok_or_else(|| "the file can't be read".to_owned())
+                .and_then(|text| model.bookmarks.import(&text));
+            if import_result.is_ok() {
+                model.bookmarks.save();
+            }
+            if let Some(panel) = &mut model.bookmarks_panel {
+                panel.import_result = Some(import_result);
+            }
+        },
+        Msg::TextSelectionChanged(text_selection) => {
+            if model.text_selection.is_none() && text_selection.is_none() {
+                // Don't rerender on every click and key press in the guide.
+                orders.skip();
+            }
+            model.text_selection = text_selection;
+        },
+        Msg::HighlightSelection => {
+            if let (
+                Page::Guide {
+                    guide,
+                    ..
+                },
+                Some(text_selection),
+            ) = (model.page, model.text_selection.take())
+            {
+                model.annotations.add(&guide, text_selection);
+                model.annotations.save();
+                annotations::clear_text_selection();
+            }
+        },
+        Msg::AnnotateSelection => {
+            if let (
+                Page::Guide {
+                    guide,
+                    ..
+                },
+                Some(text_selection),
+            ) = (model.page, model.text_selection.take())
+            {
+                let index = model.annotations.add(&guide, text_selection);
+                model.annotations.save();
+                annotations::clear_text_selection();
+                model.annotation_editor = Some(AnnotationEditor {
+                    index,
+                    note: String::new(),
+                    is_new: true,
+                });
+                focus_annotation_note_input(model, orders);
+            }
+        },
+        Msg::EditAnnotation(index) => {
+            if let Some(annotation) = model.annotations.get(index) {
+                model.annotation_editor = Some(AnnotationEditor {
+                    index,
+                    note: annotation.note.clone().unwrap_or_default(),
+                    is_new: false,
+                });
+                focus_annotation_note_input(model, orders);
+            }
+        },
+        Msg::AnnotationNoteChanged(note) => {
+            if let Some(editor) = &mut model.annotation_editor {
+                editor.note = note;
+            }
+        },
+        Msg::SaveAnnotationNote => {
+            if let Some(editor) = model.annotation_editor.take() {
+                model.annotations.set_note(editor.index, &editor.note);
+                model.annotations.save();
+            }
+        },
+        Msg::CancelAnnotationEdit => {
+            if let Some(editor) = model.annotation_editor.take() {
+                if editor.is_new {
+                    model.annotations.remove(editor.index);
+                    model.annotations.save();
+                }
+            }
+        },
+        Msg::RemoveAnnotation(index) => {
+            model.annotation_editor = None;
+            model.annotations.remove(index);
+            model.annotations.save();
+        },
+        Msg::ServiceWorkerRegistered {
+            update_waiting,
+        } => {
+            model.update_available = model.update_available || update_waiting;
+            precache_offline_guides(model, orders);
+        },
+        Msg::OfflineStatusChanged(offline_status) => {
+            model.offline_status = offline_status;
+        },
+        Msg::ToggleOfflineVersion(seed_version) => {
+            let offline_versions = &mut model.settings.offline_versions;
+            if !offline_versions.remove(seed_version.version()) {
+                offline_versions.insert(seed_version.version().to_owned());
+            }
+            save_config(model);
+            precache_offline_guides(model, orders);
+        },
+        Msg::UpdateAvailable => model.update_available = true,
+        Msg::ReloadToUpdate => offline::activate_update(orders),
+        Msg::DismissUpdate => model.update_available = false,
         Msg::SwitchVersion(version) => {
             orders
                 .notify(subs::UrlRequested::new(
@@ -373,25 +1307,139 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 }
             }
         },
+        Msg::GoToUrl(url) => {
+            orders.notify(subs::UrlRequested::new(url));
+        },
+        Msg::ToggleCommandPalette => {
+            if model.command_palette.is_some() {
+                model.command_palette = None;
+            } else {
+                model.command_palette = Some(CommandPalette::default());
+                let input = model.command_palette_input.clone();
+                orders.after_next_render(move |_| {
+                    if let Some(input) = input.get() {
+                        input.focus().expect("focus command palette input");
+                    }
+                });
+            }
+        },
+        Msg::CloseCommandPalette => {
+            model.command_palette = None;
+        },
+        Msg::CommandPaletteQueryChanged(query) => {
+            if let Some(palette) = &mut model.command_palette {
+                palette.query = query;
+                palette.selected_index = 0;
+            }
+        },
+        Msg::SelectPreviousCommand(command_count) => {
+            if let Some(palette) = &mut model.command_palette {
+                if command_count > 0 {
+                    palette.selected_index = palette
+                        .selected_index
+                        .checked_sub(1)
+                        .unwrap_or(command_count - 1);
+                }
+            }
+        },
+        Msg::SelectNextCommand(command_count) => {
+            if let Some(palette) = &mut model.command_palette {
+                if command_count > 0 {
+                    palette.selected_index =
+                        (palette.selected_index + 1) % command_count;
+                }
+            }
+        },
+        Msg::ExecuteSelectedCommand => {
+            if let Some(palette) = &model.command_palette {
+                orders.send_msg(Msg::ExecuteCommand(palette.selected_index));
+            }
+        },
+        Msg::ExecuteCommand(index) => {
+            if let Some(palette) = model.command_palette.take() {
+                if let Some(command) = command_palette::matched_commands(
+                    &palette, model,
+                )
+                .into_iter()
+                .nth(index)
+                {
+                    orders.send_msg(command.into_msg(&model.base_url));
+                }
+            }
+        },
+        Msg::KeyDown(event) => {
+            if let Some(action) = model.shortcuts.handle(&event) {
+                // E.g. don't focus the browser's search bar on `Ctrl+K`.
+                event.prevent_default();
+                orders.send_msg(action.msg());
+            }
+        },
+        Msg::FocusSearch => {
+            model.guide_list_visibility = Visible;
+            let search_input = model.search_input.clone();
+            orders.after_next_render(move |_| {
+                if let Some(search_input) = search_input.get() {
+                    search_input.focus().expect("focus search input");
+                }
+            });
+        },
+        Msg::ToggleShortcutHelp => {
+            model.shortcut_help = match model.shortcut_help {
+                Some(_) => None,
+                None => Some(ShortcutHelp::default()),
+            };
+        },
+        Msg::CloseOverlays => {
+            model.shortcut_help = None;
+            model.command_palette = None;
+            model.bookmarks_panel = None;
+            model.text_selection = None;
+            if model.annotation_editor.is_some() {
+                orders.send_msg(Msg::CancelAnnotationEdit);
+            }
+        },
+        Msg::ChangeShortcut(action, binding) => {
+            if let Some(help) = &mut model.shortcut_help {
+                if model.shortcuts.set_override(action, &binding) {
+                    help.invalid_binding = None;
+                    save_config(model);
+                } else {
+                    help.invalid_binding = Some(action);
+                }
+            }
+        },
+        Msg::ResetShortcut(action) => {
+            model.shortcuts.reset_override(action);
+            if let Some(help) = &mut model.shortcut_help {
+                help.invalid_binding = None;
+            }
+            save_config(model);
+        },
     }
 }
 
-fn search(guides: &[Guide], query: &str) -> Vec<Guide> {
-    if query.is_empty() {
-        return Vec::new();
-    }
-
-    let query = query.to_lowercase();
-    guides
-        .iter()
-        .filter_map(|guide| {
-            if guide.lowercase_text.contains(&query) {
-                Some(*guide)
-            } else {
-                None
+/// Returns `false` if the active match isn't rendered yet.
+fn scroll_to_search_match(model: &Model) -> bool {
+    let highlight = match &model.guide_highlight {
+        Some(highlight) => highlight,
+        None => return true,
+    };
+    let id = format!(
+        "{}{}",
+        search::SEARCH_MATCH_ID_PREFIX,
+        highlight.active_match
+    );
+    match document().get_element_by_id(&id) {
+        Some(mark) => {
+            // Matches in collapsed `<details>` wouldn't be visible.
+            if let Ok(Some(details)) = mark.closest("details") {
+                details.set_attribute("open", "").expect("open details");
             }
-        })
-        .collect()
+            mark.scroll_into_view();
+            true
+        },
+        None => false,
+    }
 }
 
 // ------ ------
@@ -407,11 +1455,27 @@ pub fn view(model: &Model) -> Vec<Node<Msg>> {
                     guide,
                     show_intro,
                 } => page::guide::view(&guide, model, show_intro),
+                Page::Search {
+                    seed_version,
+                    page_number,
+                } => page::search::view(seed_version, page_number, model),
+                Page::Settings => page::settings::view(model),
+                Page::AllGuides {
+                    seed_version,
+                } => page::all_guides::view(seed_version, model),
+                Page::Changelog {
+                    filter,
+                } => page::changelog::view(filter, model),
+                Page::Glossary => page::glossary::view(model),
                 Page::NotFound => page::not_found::view(&model.base_url),
             },
             page::partial::header::view(model),
+            command_palette::view(model),
+            shortcut_help::view(model),
+            bookmarks_panel::view(model),
+            annotation_editor::view(model),
+            update_prompt::view(model),
         ],
-        blender::view_for_content(model.mode),
     ]
 }
 
@@ -0,0 +1,302 @@
+use crate::{guide::Guide, Msg, Urls, DEFAULT_SEED_VERSION};
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeSet, rc::Rc};
+
+/// Emitted by `configs/webpack.config.js` from `entries/service-worker.js`.
+const SERVICE_WORKER_URL: &str = "/service-worker.js";
+const MANIFEST_LINK_SELECTOR: &str = r#"link[rel="manifest"]"#;
+
+// ------ OfflineStatus ------
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum OfflineStatus {
+    /// The browser doesn't support service workers or the page is prerendered.
+    Unsupported,
+    Caching {
+        cached: usize,
+        total: usize,
+    },
+    Available,
+    /// No Seed version is selected for offline reading.
+    NothingSaved,
+    Failed,
+}
+
+impl OfflineStatus {
+    pub fn label(self) -> String {
+        match self {
+            Self::Unsupported => "Offline reading isn't supported".to_owned(),
+            Self::Caching {
+                cached,
+                total,
+            } => format!("Saving for offline reading ({}/{})", cached, total),
+            Self::Available => "Available offline".to_owned(),
+            Self::NothingSaved => {
+                "No guides are saved for offline reading".to_owned()
+            },
+            Self::Failed => "Some guides couldn't be saved offline".to_owned(),
+        }
+    }
+}
+
+// ------ worker messages ------
+
+/// Messages for `entries/service-worker.js`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum ToWorker {
+    PrecacheGuides {
+        urls: Vec<String>,
+    },
+    SkipWaiting,
+}
+
+/// Messages from `entries/service-worker.js`.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum FromWorker {
+    PrecacheProgress {
+        cached: usize,
+        total: usize,
+    },
+    PrecacheFailed,
+}
+
+impl FromWorker {
+    fn into_msg(self) -> Msg {
+        match self {
+            Self::PrecacheProgress {
+                cached,
+                total,
+            } => Msg::OfflineStatusChanged(if total == 0 {
+                OfflineStatus::NothingSaved
+            } else if cached < total {
+                OfflineStatus::Caching {
+                    cached,
+                    total,
+                }
+            } else {
+                OfflineStatus::Available
+            }),
+            Self::PrecacheFailed => {
+                Msg::OfflineStatusChanged(OfflineStatus::Failed)
+            },
+        }
+    }
+}
+
+// ------ service worker ------
+
+/// Registers the service worker and sends its messages to the app.
+/// Returns `false` if service workers aren't supported.
+pub fn register_service_worker(orders: &mut impl Orders<Msg>) -> bool {
+    let navigator = window().navigator();
+    if !js_sys::Reflect::has(&navigator, &"serviceWorker".into())
+        .unwrap_or_default()
+    {
+        return false;
+    }
+    let container = navigator.service_worker();
+
+    let msg_sender = orders.msg_sender();
+    let update_msg_sender = msg_sender.clone();
+    let on_message =
+        Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
+            let message = js_sys::JSON::stringify(&event.data())
+                .ok()
+                .and_then(|message| message.as_string())
+                .and_then(|message| {
+                    serde_json::from_str::<FromWorker>(&message).ok()
+                });
+            msg_sender(message.map(FromWorker::into_msg));
+        }) as Box<dyn Fn(web_sys::MessageEvent)>);
+    container
+        .add_event_listener_with_callback(
+            "message",
+            on_message.as_ref().unchecked_ref(),
+        )
+        .expect("add service worker message listener");
+    // The listener lives as long as the app.
+    on_message.forget();
+
+    let registration = container.register(SERVICE_WORKER_URL);
+    orders.perform_cmd(async move {
+        match JsFuture::from(registration).await {
+            Ok(registration) => {
+                let registration: web_sys::ServiceWorkerRegistration =
+                    registration.unchecked_into();
+                watch_updates(&registration, update_msg_sender);
+                // A new version has been installed before this page was loaded.
+                Msg::ServiceWorkerRegistered {
+                    update_waiting: registration.waiting().is_some(),
+                }
+            },
+            Err(error) => {
+                error!("Service worker registration failed", error);
+                Msg::OfflineStatusChanged(OfflineStatus::Failed)
+            },
+        }
+    });
+    true
+}
+
+/// Sends `Msg::UpdateAvailable` when a new version of the service worker
+/// is installed and waits until it's activated by `activate_update`.
+fn watch_updates(
+    registration: &web_sys::ServiceWorkerRegistration,
+    msg_sender: Rc<dyn Fn(Option<Msg>)>,
+) {
+    let installing_registration = registration.clone();
+    let on_update_found = Closure::wrap(Box::new(move || {
+        let worker = match installing_registration.installing() {
+            Some(worker) => worker,
+            None => return,
+        };
+        let installing_worker = worker.clone();
+        let msg_sender = msg_sender.clone();
+        let on_state_change = Closure::wrap(Box::new(move || {
+            let installed = installing_worker.state()
+                == web_sys::ServiceWorkerState::Installed;
+            // Nothing is updated by the first installation,
+            // the page isn't controlled by any worker yet.
+            let is_update =
+                window().navigator().service_worker().controller().is_some();
+            if installed && is_update {
+                msg_sender(Some(Msg::UpdateAvailable));
+            }
+        }) as Box<dyn Fn()>);
+        worker
+            .set_onstatechange(Some(on_state_change.as_ref().unchecked_ref()));
+        // The listener lives as long as the worker.
+        on_state_change.forget();
+    }) as Box<dyn Fn()>);
+    registration
+        .set_onupdatefound(Some(on_update_found.as_ref().unchecked_ref()));
+    // The listener lives as long as the app.
+    on_update_found.forget();
+}
+
+/// Asks the service worker to cache guides of the given Seed versions.
+/// It reports the progress with messages.
+pub fn precache_guides(
+    guides: &[Guide],
+    seed_versions: &BTreeSet<String>,
+    base_url: &Url,
+    orders: &mut impl Orders<Msg>,
+) {
+    let urls = guides
+        .iter()
+        .filter(|guide| seed_versions.contains(guide.seed_version))
+        .map(|guide| Urls::new(base_url).guide(guide).to_string())
+        .collect();
+    post_message(
+        ToWorker::PrecacheGuides {
+            urls,
+        },
+        orders,
+    );
+}
+
+/// Activates the waiting service worker and reloads the page
+/// when the worker takes control.
+pub fn activate_update(orders: &mut impl Orders<Msg>) {
+    let container = window().navigator().service_worker();
+    let ready = container.ready().expect("get service worker ready promise");
+    orders.perform_cmd(async move {
+        let registration: web_sys::ServiceWorkerRegistration =
+            JsFuture::from(ready).await.ok()?.unchecked_into();
+        let worker = match registration.waiting() {
+            Some(worker) => worker,
+            // Another tab has already activated the update.
+            None => return Some(Msg::DismissUpdate),
+        };
+        let on_controller_change = Closure::once_into_js(|| {
+            window().location().reload().expect("reload page");
+        });
+        container.set_oncontrollerchange(Some(
+            on_controller_change.unchecked_ref(),
+        ));
+        send(&worker, &ToWorker::SkipWaiting);
+        None
+    });
+}
+
+/// Posts the message to the active service worker.
+fn post_message(message: ToWorker, orders: &mut impl Orders<Msg>) {
+    let ready = window()
+        .navigator()
+        .service_worker()
+        .ready()
+        .expect("get service worker ready promise");
+    orders.perform_cmd(async move {
+        let registration: web_sys::ServiceWorkerRegistration =
+            JsFuture::from(ready).await.ok()?.unchecked_into();
+        send(&registration.active()?, &message);
+        None::<Msg>
+    });
+}
+
+fn send(worker: &web_sys::ServiceWorker, message: &ToWorker) {
+    let message = js_sys::JSON::parse(
+        &serde_json::to_string(message).expect("serialize worker message"),
+    )
+    .expect("parse worker message");
+    if let Err(error) = worker.post_message(&message) {
+        error!("Message can't be sent to service worker", error);
+    }
+}
+
+// ------ manifest ------
+
+/// Replaces the static `site.webmanifest` with a manifest
+/// that contains shortcuts to the guides of the latest Seed version.
+pub fn set_manifest(guides: &[Guide], base_url: &Url) {
+    // Relative URLs can't be resolved in a `data:` manifest.
+    let origin = window().location().origin().expect("get location origin");
+    let absolute_url = |path: String| format!("{}{}", origin, path);
+    let shortcuts = guides
+        .iter()
+        .filter(|guide| guide.seed_version == DEFAULT_SEED_VERSION.version())
+        .map(|guide| {
+            json!({
+                "name": guide.menu_title,
+                "url": absolute_url(Urls::new(base_url).guide(guide).to_string()),
+            })
+        })
+        .collect::<Vec<_>>();
+    let manifest = json!({
+        "name": "Seed",
+        "short_name": "Seed",
+        "start_url": absolute_url(Urls::new(base_url).home().to_string()),
+        "scope": absolute_url("/".to_owned()),
+        "display": "standalone",
+        "theme_color": "#ffffff",
+        "background_color": "#ffffff",
+        "icons": [
+            {
+                "src": absolute_url("/android-chrome-192x192.png".to_owned()),
+                "sizes": "192x192",
+                "type": "image/png",
+            },
+            {
+                "src": absolute_url("/android-chrome-512x512.png".to_owned()),
+                "sizes": "512x512",
+                "type": "image/png",
+            },
+        ],
+        "shortcuts": shortcuts,
+    });
+
+    let manifest_link = match document().query_selector(MANIFEST_LINK_SELECTOR)
+    {
+        Ok(Some(manifest_link)) => manifest_link,
+        _ => return,
+    };
+    let href = format!(
+        "data:application/manifest+json,{}",
+        String::from(js_sys::encode_uri_component(&manifest.to_string()))
+    );
+    manifest_link.set_attribute("href", &href).expect("set manifest href");
+}
@@ -0,0 +1,181 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    generated::css_classes::C,
+    page::partial::guide_list,
+    prerender::{href, raw_html},
+    Guide, Model, Msg, SeedVersion, Urls,
+};
+use seed::{prelude::*, *};
+
+/// All guides of the Seed version in one document for printing.
+pub fn view(seed_version: SeedVersion, model: &Model) -> Node<Msg> {
+    let guides = model
+        .guides
+        .iter()
+        .filter(|guide| guide.seed_version == seed_version.version())
+        .collect::<Vec<_>>();
+    div![
+        C![
+            C.mx_auto,
+            C.px_2,
+            C.mt_16,
+            // lg__
+            C.lg__mt_0,
+            C.container,
+        ],
+        guide_list::view(None, model),
+        div![
+            C![
+                C.bg_white,
+                // lg__
+                C.lg__pt_24,
+                C.content_container,
+            ],
+            el_ref(&model.guide_content_el),
+            div![
+                C![
+                    C.flex,
+                    C.justify_between,
+                    C.items_center,
+                    C.mt_6,
+                    C.lg__mt_0,
+                ],
+                h1![
+                    C![C.text_3xl, C.font_bold,],
+                    "Seed ",
+                    seed_version.version(),
+                    " guides"
+                ],
+                view_print_button(),
+            ],
+            view_table_of_contents(&guides, seed_version, model),
+            guides.iter().map(|guide| view_guide(guide, seed_version, model)),
+        ]
+    ]
+}
+
+fn view_print_button() -> Node<Msg> {
+    button![
+        C![
+            C.print_hidden,
+            C.script_only,
+            C.px_3,
+            C.py_1,
+            C.text_sm,
+            C.border,
+            C.rounded,
+            C.text_green_700,
+            C.border_green_500,
+            C.hover__bg_green_100,
+            C.focus__outline_none,
+        ],
+        ev(Ev::Click, |_| {
+            window().print().expect("print page");
+        }),
+        "Print / Save as PDF",
+    ]
+}
+
+// ------ view table of contents ------
+
+/// Links contain the page URL because of `<base href="/">`.
+fn view_table_of_contents(
+    guides: &[&Guide],
+    seed_version: SeedVersion,
+    model: &Model,
+) -> Node<Msg> {
+    let anchor_href = |anchor: &str| {
+        href(
+            &Urls::new(&model.base_url)
+                .all_guides(seed_version)
+                .set_hash(anchor),
+        )
+    };
+    nav![
+        C![C.mt_6, C.mb_8,],
+        attrs! {
+            At::Custom("aria-label".into()) => "Table of contents",
+        },
+        h2![C![C.mb_2, C.text_xl, C.font_bold,], "Contents"],
+        ol![
+            C![C.list_decimal, C.list_inside,],
+            guides.iter().map(|guide| {
+                li![
+                    C![C.py_1,],
+                    a![
+                        C![C.text_green_700, C.hover__underline,],
+                        attrs! {
+                            At::Href => anchor_href(&guide_anchor(guide)),
+                        },
+                        guide.menu_title,
+                    ],
+                    ul![
+                        C![C.pl_8, C.text_sm,],
+                        guide
+                            .headings()
+                            .filter(|heading| heading.level == 2)
+                            .map(|heading| {
+                                let anchor = heading_anchor(guide, heading.id);
+                                li![a![
+                                    C![C.text_gray_700, C.hover__underline,],
+                                    attrs! {
+                                        At::Href => anchor_href(&anchor),
+                                    },
+                                    heading.title,
+                                ]]
+                            }),
+                    ],
+                ]
+            }),
+        ],
+    ]
+}
+
+// ------ view guide ------
+
+fn view_guide(
+    guide: &Guide,
+    seed_version: SeedVersion,
+    model: &Model,
+) -> Node<Msg> {
+    let page_href = href(&Urls::new(&model.base_url).all_guides(seed_version));
+    section![
+        C![
+            C.print_page_break,
+            C.mt_16,
+            C.markdown_body,
+            model.settings.code_theme.class(),
+        ],
+        attrs! {
+            At::Id => guide_anchor(guide),
+        },
+        style! {
+            St::FontSize => model.settings.font_size.css_value(),
+            St::MaxWidth => model.settings.content_width.css_value(),
+        },
+        raw_html(&prefix_anchors(guide, &page_href)),
+    ]
+}
+
+fn guide_anchor(guide: &Guide) -> String {
+    format!("guide-{}", guide.slug)
+}
+
+/// Heading ids are unique only in their guide.
+fn heading_anchor(guide: &Guide, heading_id: &str) -> String {
+    format!("{}--{}", guide.slug, heading_id)
+}
+
+/// Prefixes heading ids and links to them with the guide slug.
+/// Links get `page_href` because of `<base href="/">`.
+fn prefix_anchors(guide: &Guide, page_href: &str) -> String {
+    guide
+        .html
+        .replace(r#" id=""#, &format!(r#" id="{}--"#, guide.slug))
+        .replace(
+            r##" href="#"##,
+            &format!(r##" href="{}#{}--"##, page_href, guide.slug),
+        )
+}
@@ -0,0 +1,297 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    changelog::{self, Entry, EntryKind, Filter, Release, Version},
+    generated::css_classes::C,
+    page::partial::guide_list,
+    prerender::{href, raw_html},
+    Model, Msg, Urls, CHANGELOG_FEED_PATH_PART, CHANGELOG_FROM_PARAM,
+    CHANGELOG_KIND_PARAM, CHANGELOG_TO_PARAM,
+};
+use seed::{prelude::*, *};
+
+/// Releases with entries filtered by version range and kind.
+pub fn view(filter: Filter, model: &Model) -> Node<Msg> {
+    div![
+        C![
+            C.mx_auto,
+            C.px_2,
+            C.mt_16,
+            // lg__
+            C.lg__mt_0,
+            C.container,
+        ],
+        guide_list::view(None, model),
+        view_content(filter, model),
+    ]
+}
+
+fn view_content(filter: Filter, model: &Model) -> Node<Msg> {
+    let releases = changelog::filtered_releases(filter);
+    div![
+        C![
+            C.bg_white,
+            // lg__
+            C.lg__pt_24,
+            C.content_container,
+        ],
+        el_ref(&model.guide_content_el),
+        div![
+            C![
+                C.flex,
+                C.justify_between,
+                C.items_center,
+                C.mt_6,
+                C.mb_4,
+                C.lg__mt_0,
+            ],
+            h1![C![C.text_3xl, C.font_bold,], "Changelog"],
+            a![
+                C![C.text_sm, C.text_green_700, C.hover__underline,],
+                attrs! {
+                    At::Href => format!("/{}", CHANGELOG_FEED_PATH_PART),
+                    At::Type => "application/atom+xml",
+                },
+                "Atom feed",
+            ],
+        ],
+        view_scope_note(),
+        view_version_range(filter, &model.base_url),
+        view_kind_filter(filter, &model.base_url),
+        if releases.is_empty() {
+            p![C![C.my_8, C.text_gray_600,], "No changes found."]
+        } else {
+            div![releases.iter().map(view_release)]
+        },
+    ]
+}
+
+// ------ view scope note ------
+
+/// The changelog comes with the 0.6.0 guides, newer releases are only on GitHub.
+fn view_scope_note() -> Node<Msg> {
+    let latest_version =
+        changelog::releases().first().map(|release| release.version);
+    p![
+        C![C.mb_4, C.text_sm, C.text_gray_600,],
+        if let Some(latest_version) = latest_version {
+            format!("Releases up to Seed {}. ", latest_version)
+        } else {
+            String::new()
+        },
+        "Newer releases are listed in ",
+        a![
+            C![C.text_green_700, C.hover__underline,],
+            attrs! {
+                At::Href => "https://github.com/seed-rs/seed/blob/master/CHANGELOG.md",
+            },
+            "Seed's changelog on GitHub",
+        ],
+        ".",
+    ]
+}
+
+// ------ view version range ------
+
+fn view_version_range(filter: Filter, base_url: &Url) -> Node<Msg> {
+    let versions = changelog::releases()
+        .iter()
+        .map(|release| release.version)
+        .collect::<Vec<_>>();
+
+    form![
+        C![C.flex, C.flex_wrap, C.items_center, C.mb_4, C.text_sm,],
+        // The form is submitted only without scripts,
+        // the app changes the URL when a version is selected.
+        attrs! {
+            At::Action => href(&Urls::new(base_url).changelog(Filter::default())),
+            At::Method => "get",
+        },
+        ev(Ev::Submit, |event| event.prevent_default()),
+        view_version_select(
+            "From",
+            CHANGELOG_FROM_PARAM,
+            filter.from,
+            &versions,
+            base_url,
+            move |from| Filter {
+                from,
+                ..filter
+            },
+        ),
+        view_version_select(
+            "To",
+            CHANGELOG_TO_PARAM,
+            filter.to,
+            &versions,
+            base_url,
+            move |to| Filter {
+                to,
+                ..filter
+            },
+        ),
+        filter.kind.map(|kind| {
+            input![attrs! {
+                At::Type => "hidden",
+                At::Name => CHANGELOG_KIND_PARAM,
+                At::Value => kind.id(),
+            }]
+        }),
+        button![
+            C![
+                C.no_script_only,
+                C.px_3,
+                C.py_1,
+                C.border,
+                C.rounded,
+                C.text_green_700,
+                C.border_green_500,
+                C.hover__bg_green_100,
+                C.focus__outline_none,
+            ],
+            attrs! {
+                At::Type => "submit",
+            },
+            "Apply",
+        ],
+    ]
+}
+
+fn view_version_select(
+    label: &str,
+    name: &str,
+    selected: Option<Version>,
+    versions: &[Version],
+    base_url: &Url,
+    with_version: impl FnOnce(Option<Version>) -> Filter + Clone + 'static,
+) -> Node<Msg> {
+    let base_url = base_url.clone();
+    label![
+        C![C.mr_4, C.mb_2,],
+        span![C![C.mr_2, C.text_gray_700,], label],
+        select![
+            C![
+                C.px_2,
+                C.py_1,
+                C.border,
+                C.border_gray_400,
+                C.rounded,
+                C.focus__outline_none,
+            ],
+            attrs! {
+                At::Name => name,
+            },
+            ev(Ev::Change, move |event| {
+                let value = event
+                    .target()
+                    .and_then(|target| seed::util::get_value(&target).ok())
+                    .unwrap_or_default();
+                let filter = with_version(Version::parse(&value));
+                Msg::GoToUrl(Urls::new(&base_url).changelog(filter))
+            }),
+            option![
+                attrs! {
+                    At::Value => "",
+                    At::Selected => selected.is_none().as_at_value(),
+                },
+                "Any version",
+            ],
+            versions.iter().map(|version| {
+                option![
+                    attrs! {
+                        At::Value => version.to_string(),
+                        At::Selected => (selected == Some(*version)).as_at_value(),
+                    },
+                    version.to_string(),
+                ]
+            }),
+        ],
+    ]
+}
+
+// ------ view kind filter ------
+
+fn view_kind_filter(filter: Filter, base_url: &Url) -> Node<Msg> {
+    let view_filter_link = |title: &str, kind: Option<EntryKind>| {
+        let active = kind == filter.kind;
+        a![
+            C![
+                C.mr_2,
+                C.mb_2,
+                C.px_3,
+                C.py_1,
+                C.text_sm,
+                C.rounded_full,
+                C.focus__outline_none,
+                if active {
+                    vec![C.bg_green_200, C.text_green_900, C.font_bold]
+                } else {
+                    vec![C.bg_gray_200, C.text_gray_700, C.hover__bg_green_100]
+                },
+            ],
+            attrs! {
+                At::Href => href(&Urls::new(base_url).changelog(Filter { kind, ..filter })),
+            },
+            format!("{} ({})", title, changelog::count(filter, kind)),
+        ]
+    };
+
+    div![
+        C![C.flex, C.flex_wrap, C.mb_6,],
+        view_filter_link("All", None),
+        EntryKind::ALL
+            .iter()
+            .map(|kind| view_filter_link(kind.label(), Some(*kind))),
+    ]
+}
+
+// ------ view release ------
+
+fn view_release(release: &Release) -> Node<Msg> {
+    section![
+        C![C.mb_8,],
+        h2![
+            C![C.flex, C.items_baseline, C.mb_2, C.text_2xl, C.font_bold,],
+            format!("Seed {}", release.version),
+            release.date.map(|date| {
+                span![
+                    C![C.ml_3, C.text_sm, C.font_normal, C.text_gray_600,],
+                    date
+                ]
+            }),
+        ],
+        ul![release.entries.iter().map(view_entry)],
+    ]
+}
+
+fn view_entry(entry: &Entry) -> Node<Msg> {
+    let breaking = entry.kind == EntryKind::Breaking;
+    li![
+        C![
+            C.markdown_body,
+            C.mb_1,
+            C.py_1,
+            C.px_3,
+            C.border_l_4,
+            if breaking {
+                vec![C.bg_red_100, C.border_red_500]
+            } else {
+                vec![C.border_transparent]
+            },
+        ],
+        IF!(breaking => span![
+            C![
+                C.mr_2,
+                C.px_2,
+                C.text_xs,
+                C.font_bold,
+                C.rounded_full,
+                C.bg_red_200,
+                C.text_red_800,
+            ],
+            "Breaking",
+        ]),
+        raw_html(entry.html),
+    ]
+}
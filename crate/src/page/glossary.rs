@@ -0,0 +1,89 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    generated::css_classes::C,
+    glossary::{self, Term},
+    page::partial::guide_list,
+    prerender::{href, raw_html},
+    Model, Msg, Urls,
+};
+use seed::{prelude::*, *};
+
+/// Seed terms with definitions and links to guides
+/// in the selected Seed version.
+pub fn view(model: &Model) -> Node<Msg> {
+    div![
+        C![
+            C.mx_auto,
+            C.px_2,
+            C.mt_16,
+            // lg__
+            C.lg__mt_0,
+            C.container,
+        ],
+        guide_list::view(None, model),
+        view_content(model),
+    ]
+}
+
+fn view_content(model: &Model) -> Node<Msg> {
+    div![
+        C![
+            C.bg_white,
+            // lg__
+            C.lg__pt_24,
+            C.content_container,
+        ],
+        el_ref(&model.guide_content_el),
+        h1![
+            C![C.text_3xl, C.font_bold, C.mt_6, C.mb_4, C.lg__mt_0,],
+            "Glossary"
+        ],
+        p![
+            C![C.mb_6, C.text_sm, C.text_gray_600,],
+            "Terms are also explained in tooltips where they first appear in guides.",
+        ],
+        dl![glossary::terms()
+            .iter()
+            .map(|term| view_term(term, model))],
+    ]
+}
+
+fn view_term(term: &Term, model: &Model) -> Node<Msg> {
+    let guide = term.guide(&model.guides, model.selected_seed_version);
+    div![
+        C![C.markdown_body, C.mb_6,],
+        dt![
+            C![C.mb_1, C.text_xl, C.font_bold,],
+            id!(term.id),
+            raw_html(term.html),
+        ],
+        dd![
+            raw_html(term.definition_html),
+            guide.map(|guide| {
+                let url = match term.heading_id {
+                    Some(heading_id) => {
+                        Urls::new(&model.base_url).heading(guide, heading_id)
+                    },
+                    None => Urls::new(&model.base_url).guide(guide),
+                };
+                p![
+                    C![C.mt_1, C.text_sm, C.text_gray_600,],
+                    "Explained in ",
+                    a![
+                        C![C.text_green_700, C.hover__underline,],
+                        attrs! {
+                            At::Href => href(&url),
+                        },
+                        format!(
+                            "{} ({})",
+                            guide.menu_title, guide.seed_version
+                        ),
+                    ],
+                    ".",
+                ]
+            }),
+        ],
+    ]
+}
@@ -2,9 +2,11 @@
 #![allow(clippy::cognitive_complexity)]
 
 use crate::{
+    annotations, bookmarks,
     generated::css_classes::C,
     page::partial::{content_control_panel, guide_list, intro},
-    Guide, Model, Msg,
+    prerender::{href, raw_html},
+    search, Guide, GuideHighlight, Model, Msg, Theme, Urls,
 };
 use seed::{prelude::*, *};
 
@@ -18,7 +20,7 @@ pub fn view(guide: &Guide, model: &Model, show_intro: bool) -> Node<Msg> {
             C.lg__mt_0,
             C.container,
         ],
-        guide_list::view(guide, model),
+        guide_list::view(Some(guide), model),
         view_content(guide, model, show_intro, &model.guide_content_el),
     ]
 }
@@ -38,78 +40,355 @@ fn view_content(
             C.content_container,
         ],
         el_ref(guide_content_el),
-        IF!(model.in_prerendering => view_loading_warning()),
         intro::view(
             show_intro,
             &model.base_url,
             &model.guides,
             model.selected_seed_version
         ),
+        IF!(show_intro => view_continue_reading(model)),
         content_control_panel::view(guide, Top, model),
-        view_guide_html(guide.html),
+        model.guide_highlight.as_ref().map(view_highlight_bar),
+        view_guide_html(guide, model),
+        IF!(model.text_selection.is_some() => view_selection_toolbar()),
+        view_notes(guide, model),
+        div![
+            C![C.script_only, C.flex, C.justify_center, C.mt_8,],
+            view_completion_toggle(model.reading_progress.is_completed(guide)),
+            view_bookmark_toggle(model.bookmarks.contains(
+                guide,
+                None,
+                &model.guides
+            )),
+        ],
         content_control_panel::view(guide, Bottom, model),
-        view_platform_logos()
+        view_platform_logos(model.theme())
     ]
 }
 
-fn view_loading_warning() -> Node<Msg> {
-    div![
-        raw![
-            r#"
-            <style>
-                @keyframes loading-warning-display {
-                    from { height: 0; opacity: 0; }
-                    to { height: auto; opacity: 1; }
-                }
-            </style>
-            "#
+// ------ view continue reading ------
+
+fn view_continue_reading(model: &Model) -> Node<Msg> {
+    let guide = if let Some(guide) = model
+        .reading_progress
+        .last_visited(&model.guides, model.selected_seed_version.version())
+    {
+        guide
+    } else {
+        return empty![];
+    };
+    a![
+        C![
+            C.flex,
+            C.justify_between,
+            C.items_center,
+            C.mb_8,
+            C.px_4,
+            C.py_3,
+            C.rounded,
+            C.bg_green_100,
+            C.text_green_800,
+            C.hover__bg_green_200,
+            C.focus__outline_none,
         ],
-        style! {
-            St::AnimationName => "loading-warning-display",
-            St::AnimationDelay => "5s",
-            St::AnimationDuration => "3s",
-            St::AnimationFillMode => "both",
-            St::AnimationTimingFunction => "ease-in",
-            St::Overflow => "hidden",
+        attrs! {
+            At::Href => href(&Urls::new(&model.base_url).guide(guide)),
         },
-        div![
+        span!["Continue where you left off"],
+        span![C![C.font_bold,], guide.menu_title, " \u{2192}"],
+    ]
+}
+
+// ------ view selection toolbar ------
+
+fn view_selection_toolbar() -> Node<Msg> {
+    let view_button = |title: &str, msg: fn() -> Msg| {
+        button![
             C![
-                C.bg_blue_500,
+                C.px_3,
+                C.py_1,
                 C.text_white,
-                C.text_sm,
-                C.p_4,
-                C.mb_8,
+                C.hover__underline,
+                C.focus__outline_none,
+            ],
+            ev(Ev::Click, move |_| msg()),
+            title,
+        ]
+    };
+
+    div![
+        C![
+            C.fixed,
+            C.bottom_0,
+            C.inset_x_0,
+            C.z_40,
+            C.flex,
+            C.justify_center,
+            C.mb_4,
+        ],
+        div![
+            C![
+                C.flex,
+                C.px_2,
+                C.py_1,
+                C.rounded_full,
+                C.shadow_lg,
+                C.bg_green_700,
             ],
             attrs! {
-                At::Custom("role".into()) => "alert",
+                At::Custom("role".into()) => "toolbar",
+                At::Custom("aria-label".into()) => "Annotate selection",
             },
-            strong![C![C.font_bold,], "Loading..."],
-            p![
-                "If this message does not disappear for a long time, make sure you have a ",
-                a![
-                    C![C.underline,],
-                    attrs! {
-                        At::Href => "https://developer.mozilla.org/en-US/docs/WebAssembly#Browser_compatibility",
-                    },
-                    "browser with WebAssembly support"
+            view_button("Highlight", || Msg::HighlightSelection),
+            view_button("Add note", || Msg::AnnotateSelection),
+            view_button("Cancel", || Msg::TextSelectionChanged(None)),
+        ]
+    ]
+}
+
+// ------ view notes ------
+
+fn view_notes(guide: &Guide, model: &Model) -> Node<Msg> {
+    let annotations =
+        model.annotations.of_guide(guide, &model.guides).collect::<Vec<_>>();
+    if annotations.is_empty() {
+        return empty![];
+    }
+    let export_href = format!(
+        "data:text/markdown;charset=utf-8,{}",
+        String::from(js_sys::encode_uri_component(
+            &model.annotations.to_markdown(&model.guides)
+        ))
+    );
+    section![
+        C![C.mt_8, C.pt_4, C.border_t, C.border_gray_200, C.text_sm,],
+        div![
+            C![C.flex, C.justify_between, C.items_center, C.mb_2,],
+            h2![
+                C![C.font_bold, C.text_green_800,],
+                "Your highlights and notes"
+            ],
+            a![
+                C![C.text_green_700, C.hover__underline, C.focus__outline_none,],
+                attrs! {
+                    At::Href => export_href,
+                    At::Download => annotations::EXPORT_FILE_NAME,
+                },
+                "Export all as Markdown",
+            ],
+        ],
+        ul![annotations.into_iter().map(|(index, annotation)| {
+            li![
+                C![C.py_2, C.border_b, C.border_gray_200,],
+                button![
+                    C![
+                        C.text_left,
+                        C.text_gray_700,
+                        C.hover__text_green_700,
+                        C.focus__outline_none,
+                    ],
+                    ev(Ev::Click, move |_| Msg::EditAnnotation(index)),
+                    span![
+                        C![C.italic,],
+                        "\u{201c}",
+                        &annotation.quote,
+                        "\u{201d}"
+                    ],
+                    annotation
+                        .note
+                        .as_ref()
+                        .map(|note| div![C![C.mt_1, C.text_gray_900,], note]),
                 ],
-                " and JavaScript enabled."
             ]
+        })],
+    ]
+}
+
+// ------ view completion toggle ------
+
+fn view_completion_toggle(completed: bool) -> Node<Msg> {
+    view_toggle_button(
+        completed,
+        if completed {
+            "\u{2713} Done"
+        } else {
+            "Mark as done"
+        },
+        || Msg::ToggleGuideCompleted,
+    )
+}
+
+// ------ view bookmark toggle ------
+
+fn view_bookmark_toggle(bookmarked: bool) -> Node<Msg> {
+    view_toggle_button(
+        bookmarked,
+        if bookmarked {
+            "\u{2605} Bookmarked"
+        } else {
+            "\u{2606} Bookmark"
+        },
+        || Msg::ToggleGuideBookmark,
+    )
+}
+
+fn view_toggle_button(
+    pressed: bool,
+    title: &str,
+    on_click: fn() -> Msg,
+) -> Node<Msg> {
+    button![
+        C![
+            C.mx_1,
+            C.px_4,
+            C.py_1,
+            C.text_sm,
+            C.border,
+            C.rounded_full,
+            C.focus__outline_none,
+            if pressed {
+                vec![C.text_green_700, C.border_green_500, C.bg_green_100]
+            } else {
+                vec![
+                    C.text_gray_600,
+                    C.border_gray_400,
+                    C.hover__text_green_700,
+                    C.hover__border_green_500,
+                ]
+            },
+        ],
+        attrs! {
+            At::Custom("aria-pressed".into()) => pressed,
+        },
+        ev(Ev::Click, move |_| on_click()),
+        title,
+    ]
+}
+
+// ------ view highlight bar ------
+
+fn view_highlight_bar(highlight: &GuideHighlight) -> Node<Msg> {
+    let view_button = |title: &str, msg: fn() -> Msg| {
+        button![
+            C![
+                C.px_2,
+                C.text_green_800,
+                C.hover__text_green_500,
+                C.hover__underline,
+                C.focus__outline_none,
+            ],
+            ev(Ev::Click, move |_| msg()),
+            title,
+        ]
+    };
+
+    div![
+        C![
+            C.sticky,
+            C.top_0,
+            C.z_10,
+            C.flex,
+            C.items_center,
+            C.justify_between,
+            C.mb_4,
+            C.px_4,
+            C.py_2,
+            C.text_sm,
+            C.bg_green_100,
+            C.border_b_2,
+            C.border_green_500,
+        ],
+        span![
+            C![C.text_green_800,],
+            if highlight.match_count == 0 {
+                format!("No matches for \"{}\"", highlight.query)
+            } else {
+                format!(
+                    "\"{}\": {} of {}",
+                    highlight.query,
+                    highlight.active_match + 1,
+                    highlight.match_count
+                )
+            }
+        ],
+        div![
+            IF!(highlight.match_count > 1 => vec![
+                view_button("Previous", || Msg::PreviousSearchMatch),
+                view_button("Next", || Msg::NextSearchMatch),
+            ]),
+            view_button("Clear", || Msg::ClearSearchHighlight),
         ]
     ]
 }
 
-fn view_guide_html(content: &str) -> Node<Msg> {
+// ------ view guide html ------
+
+fn view_guide_html(guide: &Guide, model: &Model) -> Node<Msg> {
+    let settings = &model.settings;
+    let content = model.guide_highlight.as_ref().map_or_else(
+        || guide.html.to_owned(),
+        |highlight| {
+            search::mark_matches_in_html(
+                guide.html,
+                &highlight.query,
+                highlight.active_match,
+            )
+        },
+    );
+    let content = annotations::mark_annotations_in_html(
+        &content,
+        model.annotations.of_guide(guide, &model.guides),
+    );
+    let content = bookmarks::add_heading_buttons(
+        &content,
+        guide,
+        &model.bookmarks,
+        &model.guides,
+    );
     div![
         C![
             // it has to be "markdown-body" so it's content is styled by Github CSS
             C.markdown_body,
+            settings.code_theme.class(),
         ],
-        set_el_keys(raw!(content))
+        style! {
+            St::FontSize => settings.font_size.css_value(),
+            St::MaxWidth => settings.content_width.css_value(),
+        },
+        // Heading bookmark buttons and annotations are a part of the raw HTML.
+        ev(Ev::Click, |event| {
+            let target = event.target()?;
+            let target = target.dyn_ref::<web_sys::Element>()?;
+            if let Ok(Some(button)) =
+                target.closest(&format!(".{}", C.heading_bookmark))
+            {
+                let heading_id = button.get_attribute("data-heading-id")?;
+                return Some(Msg::ToggleHeadingBookmark(heading_id));
+            }
+            let mark = target.closest(&format!(".{}", C.annotation)).ok()??;
+            let index = mark.get_attribute(annotations::INDEX_ATTRIBUTE)?;
+            Some(Msg::EditAnnotation(index.parse().ok()?))
+        }),
+        ev(Ev::MouseUp, on_text_selection(*guide)),
+        ev(Ev::KeyUp, on_text_selection(*guide)),
+        set_el_keys(raw_html(&content))
     ]
 }
 
-fn view_netlify_logo() -> Node<Msg> {
+/// Sends the text selected in the guide content, so it can be annotated.
+fn on_text_selection(
+    guide: Guide,
+) -> impl FnOnce(web_sys::Event) -> Option<Msg> + Clone {
+    move |event| {
+        let container =
+            event.current_target()?.dyn_into::<web_sys::Node>().ok()?;
+        Some(Msg::TextSelectionChanged(annotations::text_selection(
+            &container, &guide,
+        )))
+    }
+}
+
+fn view_netlify_logo(theme: Theme) -> Node<Msg> {
     a![
         C! {
             C.mx_2,
@@ -117,18 +396,17 @@ fn view_netlify_logo() -> Node<Msg> {
         attrs! {
             At::Href => "https://www.netlify.com",
         },
-        img![
-            C! {
-                C.z_auto,
-            },
-            attrs! {
-                At::Src => "https://www.netlify.com/img/global/badges/netlify-light.svg"
+        img![attrs! {
+            At::Src => if theme.is_dark() {
+                "https://www.netlify.com/img/global/badges/netlify-dark.svg"
+            } else {
+                "https://www.netlify.com/img/global/badges/netlify-light.svg"
             }
-        ],
+        }],
     ]
 }
 
-fn view_digitalocean_logo() -> Node<Msg> {
+fn view_digitalocean_logo(theme: Theme) -> Node<Msg> {
     a![
         C! {
             C.mx_2,
@@ -138,18 +416,17 @@ fn view_digitalocean_logo() -> Node<Msg> {
             // referral link from console
             At::Href => "https://m.do.co/c/f02c252209c1"
         },
-        img![
-            C! {
-                C.z_auto,
-            },
-            attrs! {
-                At::Src => "https://opensource.nyc3.cdn.digitaloceanspaces.com/attribution/assets/PoweredByDO/DO_Powered_by_Badge_black.svg"
+        img![attrs! {
+            At::Src => if theme.is_dark() {
+                "https://opensource.nyc3.cdn.digitaloceanspaces.com/attribution/assets/PoweredByDO/DO_Powered_by_Badge_white.svg"
+            } else {
+                "https://opensource.nyc3.cdn.digitaloceanspaces.com/attribution/assets/PoweredByDO/DO_Powered_by_Badge_black.svg"
             }
-        ],
+        }],
     ]
 }
 
-fn view_platform_logos() -> Node<Msg> {
+fn view_platform_logos(theme: Theme) -> Node<Msg> {
     div![
         C! {
             C.flex,
@@ -158,8 +435,8 @@ fn view_platform_logos() -> Node<Msg> {
             C.justify_center,
             C.flex_row,
         },
-        view_netlify_logo(),
-        view_digitalocean_logo(),
+        view_netlify_logo(theme),
+        view_digitalocean_logo(theme),
     ]
 }
 
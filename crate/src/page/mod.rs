@@ -1,3 +1,8 @@
+pub mod all_guides;
+pub mod changelog;
+pub mod glossary;
 pub mod guide;
 pub mod not_found;
 pub mod partial;
+pub mod search;
+pub mod settings;
@@ -1,7 +1,7 @@
 // Clippy complains about `cognitive_complexity` for simple functions with macros.
 #![allow(clippy::cognitive_complexity)]
 
-use crate::{generated::css_classes::C, Msg, Urls};
+use crate::{generated::css_classes::C, prerender::href, Msg, Urls};
 use seed::{a, attrs, div, prelude::*, C};
 
 pub fn view(base_url: &Url) -> Node<Msg> {
@@ -26,7 +26,7 @@ pub fn view(base_url: &Url) -> Node<Msg> {
                     C.hover__text_green_700,
                 ],
                 attrs! {
-                    At::Href => Urls::new(base_url).home()
+                    At::Href => href(&Urls::new(base_url).home())
                 },
                 "Home"
             ],
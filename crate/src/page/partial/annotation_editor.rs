@@ -0,0 +1,108 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{generated::css_classes::C, Model, Msg};
+use seed::{prelude::*, *};
+
+pub fn view(model: &Model) -> Node<Msg> {
+    let (editor, annotation) = match &model.annotation_editor {
+        Some(editor) => match model.annotations.get(editor.index) {
+            Some(annotation) => (editor, annotation),
+            None => return empty![],
+        },
+        None => return empty![],
+    };
+    let index = editor.index;
+
+    div![
+        C![C.fixed, C.inset_0, C.z_50, C.flex, C.justify_center, C.px_4,],
+        style! {
+            St::BackgroundColor => "rgba(0, 0, 0, 0.5)",
+        },
+        ev(Ev::Click, |_| Msg::CancelAnnotationEdit),
+        div![
+            C![
+                C.w_full,
+                C.max_w_xl,
+                C.mt_16,
+                C.self_start,
+                C.p_6,
+                C.bg_white,
+                C.rounded,
+                C.shadow_lg,
+            ],
+            attrs! {
+                At::Custom("role".into()) => "dialog",
+                At::Custom("aria-label".into()) => "Note",
+            },
+            ev(Ev::Click, |event| event.stop_propagation()),
+            h2![C![C.mb_4, C.text_2xl, C.font_bold,], "Note"],
+            blockquote![
+                C![
+                    C.mb_4,
+                    C.pl_4,
+                    C.border_l_4,
+                    C.border_green_500,
+                    C.text_gray_700,
+                ],
+                &annotation.quote,
+            ],
+            textarea![
+                C![
+                    C.w_full,
+                    C.h_32,
+                    C.p_2,
+                    C.border,
+                    C.border_gray_400,
+                    C.rounded,
+                    C.focus__outline_none,
+                    C.focus__border_green_500,
+                ],
+                el_ref(&model.annotation_note_input),
+                attrs! {
+                    At::Value => editor.note,
+                    At::Placeholder => "Your note",
+                    At::Custom("aria-label".into()) => "Note",
+                },
+                input_ev(Ev::Input, Msg::AnnotationNoteChanged),
+            ],
+            div![
+                C![C.flex, C.justify_between, C.mt_4,],
+                view_button("Delete", C.text_red_500, move || {
+                    Msg::RemoveAnnotation(index)
+                }),
+                div![
+                    view_button("Cancel", C.text_gray_600, || {
+                        Msg::CancelAnnotationEdit
+                    }),
+                    view_button("Save", C.text_green_700, || {
+                        Msg::SaveAnnotationNote
+                    }),
+                ],
+            ],
+        ]
+    ]
+}
+
+fn view_button(
+    title: &str,
+    color: &str,
+    on_click: impl FnOnce() -> Msg + Clone + 'static,
+) -> Node<Msg> {
+    button![
+        C![
+            C.ml_2,
+            C.px_3,
+            C.py_1,
+            C.text_sm,
+            C.border,
+            C.rounded,
+            C.border_gray_400,
+            C.hover__bg_gray_200,
+            C.focus__outline_none,
+            color,
+        ],
+        ev(Ev::Click, move |_| on_click()),
+        title,
+    ]
+}
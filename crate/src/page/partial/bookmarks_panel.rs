@@ -0,0 +1,208 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    bookmarks::{self, Bookmark},
+    generated::css_classes::C,
+    prerender::href,
+    BookmarksPanel, Model, Msg, Urls, SEED_VERSIONS,
+};
+use seed::{prelude::*, *};
+
+pub fn view(model: &Model) -> Node<Msg> {
+    let panel = if let Some(panel) = &model.bookmarks_panel {
+        panel
+    } else {
+        return empty![];
+    };
+
+    div![
+        C![C.fixed, C.inset_0, C.z_50, C.flex, C.justify_center, C.px_4,],
+        style! {
+            St::BackgroundColor => "rgba(0, 0, 0, 0.5)",
+        },
+        ev(Ev::Click, |_| Msg::ToggleBookmarksPanel),
+        div![
+            C![
+                C.w_full,
+                C.max_w_2xl,
+                C.mt_16,
+                C.mb_16,
+                C.self_start,
+                C.p_6,
+                C.bg_white,
+                C.rounded,
+                C.shadow_lg,
+                C.overflow_y_auto,
+            ],
+            style! {
+                St::MaxHeight => "calc(100vh - 8rem)",
+            },
+            attrs! {
+                At::Custom("role".into()) => "dialog",
+                At::Custom("aria-label".into()) => "Bookmarks",
+            },
+            ev(Ev::Click, |event| event.stop_propagation()),
+            div![
+                C![C.flex, C.justify_between, C.items_center, C.mb_4,],
+                h2![C![C.text_2xl, C.font_bold,], "Bookmarks"],
+                button![
+                    C![
+                        C.text_green_500,
+                        C.hover__text_green_700,
+                        C.hover__underline,
+                        C.focus__outline_none,
+                    ],
+                    ev(Ev::Click, |_| Msg::ToggleBookmarksPanel),
+                    "Close",
+                ],
+            ],
+            view_bookmarks(model),
+            view_transfer(panel, model),
+        ]
+    ]
+}
+
+// ------ view bookmarks ------
+
+fn view_bookmarks(model: &Model) -> Node<Msg> {
+    let bookmarks = model.bookmarks.all();
+    if bookmarks.is_empty() {
+        return p![
+            C![C.mb_4, C.text_sm, C.text_gray_600,],
+            "No bookmarks yet. Bookmark a guide under its content \
+             or a section with the star next to its heading.",
+        ];
+    }
+    div![SEED_VERSIONS.iter().filter_map(|seed_version| {
+        let version = seed_version.version();
+        let items = bookmarks
+            .iter()
+            .enumerate()
+            .filter(|(_, bookmark)| bookmark.seed_version == version)
+            .map(|(index, bookmark)| view_bookmark(index, bookmark, model))
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return None;
+        }
+        Some(section![
+            C![C.mb_4,],
+            h3![C![C.mb_1, C.font_bold, C.text_green_800,], "Seed ", version],
+            ul![C![C.text_sm,], items],
+        ])
+    })]
+}
+
+fn view_bookmark(
+    index: usize,
+    bookmark: &Bookmark,
+    model: &Model,
+) -> Node<Msg> {
+    let base_url = &model.base_url;
+    let url = bookmark.guide(&model.guides).map(|guide| {
+        bookmark.heading_id.as_ref().map_or_else(
+            || Urls::new(base_url).guide(guide),
+            |heading_id| Urls::new(base_url).heading(guide, heading_id),
+        )
+    });
+    li![
+        C![
+            C.flex,
+            C.justify_between,
+            C.items_center,
+            C.py_1,
+            C.border_b,
+            C.border_gray_200,
+        ],
+        if let Some(url) = url {
+            a![
+                C![C.text_green_700, C.hover__underline, C.focus__outline_none,],
+                attrs! {
+                    At::Href => href(&url),
+                },
+                ev(Ev::Click, |_| Msg::ToggleBookmarksPanel),
+                &bookmark.title,
+            ]
+        } else {
+            span![C![C.text_gray_600,], &bookmark.title, " (not found)",]
+        },
+        button![
+            C![
+                C.ml_4,
+                C.text_gray_600,
+                C.hover__text_red_500,
+                C.focus__outline_none,
+            ],
+            attrs! {
+                At::Custom("aria-label".into()) => format!("Remove bookmark: {}", bookmark.title),
+            },
+            ev(Ev::Click, move |_| Msg::RemoveBookmark(index)),
+            "Remove",
+        ],
+    ]
+}
+
+// ------ view transfer ------
+
+fn view_transfer(panel: &BookmarksPanel, model: &Model) -> Node<Msg> {
+    let export_href = format!(
+        "data:application/json;charset=utf-8,{}",
+        String::from(js_sys::encode_uri_component(&model.bookmarks.to_json()))
+    );
+    div![
+        C![C.mt_6, C.pt_4, C.border_t, C.border_gray_200, C.text_sm,],
+        div![
+            C![C.flex, C.flex_wrap, C.items_center,],
+            a![
+                C![
+                    C.mr_4,
+                    C.mb_2,
+                    C.px_3,
+                    C.py_1,
+                    C.border,
+                    C.rounded,
+                    C.text_green_700,
+                    C.border_green_500,
+                    C.hover__bg_green_100,
+                    C.focus__outline_none,
+                ],
+                attrs! {
+                    At::Href => export_href,
+                    At::Download => bookmarks::EXPORT_FILE_NAME,
+                },
+                "Export JSON",
+            ],
+            label![
+                C![C.mb_2, C.cursor_pointer,],
+                span![C![C.mr_2,], "Import JSON:"],
+                input![
+                    attrs! {
+                        At::Type => "file",
+                        At::Accept => "application/json,.json",
+                    },
+                    ev(Ev::Change, |event| {
+                        let input = event
+                            .target()?
+                            .dyn_into::<web_sys::HtmlInputElement>()
+                            .ok()?;
+                        let file = input.files()?.get(0)?;
+                        Some(Msg::ImportBookmarks(file))
+                    }),
+                ],
+            ],
+        ],
+        panel.import_result.as_ref().map(|result| match result {
+            Ok(count) => p![
+                C![C.text_green_700,],
+                format!("Imported {} new bookmark(s).", count),
+            ],
+            Err(error) => p![
+                C![C.text_red_500,],
+                attrs! {
+                    At::Custom("role".into()) => "alert",
+                },
+                format!("Import failed: {}", error),
+            ],
+        }),
+    ]
+}
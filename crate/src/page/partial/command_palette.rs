@@ -0,0 +1,316 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    generated::css_classes::C,
+    guide::{Guide, Heading},
+    page::partial::content_control_panel::{next_guide, previous_guide},
+    CommandPalette, Mode, Model, Msg, Page, SeedVersion, Urls, SEED_VERSIONS,
+};
+use seed::{prelude::*, *};
+
+/// Max number of commands displayed in the palette.
+const MAX_VISIBLE_COMMANDS: usize = 12;
+
+// ------ Command ------
+
+pub enum Command {
+    OpenGuide(Guide),
+    OpenHeading(Guide, Heading),
+    SwitchVersion(SeedVersion),
+    SelectMode(Mode),
+    GoToPreviousGuide(Guide),
+    GoToNextGuide(Guide),
+    ShowShortcuts,
+    OpenSettings,
+    ShowBookmarks,
+    OpenAllGuides(SeedVersion),
+}
+
+impl Command {
+    fn title(&self) -> String {
+        match self {
+            Self::OpenGuide(guide) => guide.menu_title.to_owned(),
+            Self::OpenHeading(guide, heading) => {
+                format!("{} › {}", guide.menu_title, heading.title)
+            },
+            Self::SwitchVersion(seed_version) => {
+                format!("Switch to Seed {}", seed_version.version())
+            },
+            Self::SelectMode(mode) => {
+                format!("Switch to {} mode", mode.label().to_lowercase())
+            },
+            Self::GoToPreviousGuide(guide) => {
+                format!("Previous guide: {}", guide.menu_title)
+            },
+            Self::GoToNextGuide(guide) => {
+                format!("Next guide: {}", guide.menu_title)
+            },
+            Self::ShowShortcuts => "Show keyboard shortcuts".to_owned(),
+            Self::OpenSettings => "Open settings".to_owned(),
+            Self::ShowBookmarks => "Show bookmarks".to_owned(),
+            Self::OpenAllGuides(seed_version) => {
+                format!("All {} guides on one page", seed_version.version())
+            },
+        }
+    }
+
+    const fn section(&self) -> &'static str {
+        match self {
+            Self::OpenGuide(_) => "Guide",
+            Self::OpenHeading(..) => "Heading",
+            Self::SwitchVersion(_) => "Version",
+            Self::SelectMode(_)
+            | Self::GoToPreviousGuide(_)
+            | Self::GoToNextGuide(_)
+            | Self::ShowShortcuts
+            | Self::OpenSettings
+            | Self::ShowBookmarks
+            | Self::OpenAllGuides(_) => "Action",
+        }
+    }
+
+    pub fn into_msg(self, base_url: &Url) -> Msg {
+        match self {
+            Self::OpenGuide(guide) => {
+                Msg::GoToUrl(Urls::new(base_url).guide(&guide))
+            },
+            Self::OpenHeading(guide, heading) => {
+                Msg::GoToUrl(Urls::new(base_url).heading(&guide, heading.id))
+            },
+            Self::SwitchVersion(seed_version) => {
+                Msg::SwitchVersion(seed_version)
+            },
+            Self::SelectMode(mode) => Msg::SelectMode(mode),
+            Self::GoToPreviousGuide(_) => Msg::GoToPreviousGuide,
+            Self::GoToNextGuide(_) => Msg::GoToNextGuide,
+            Self::ShowShortcuts => Msg::ToggleShortcutHelp,
+            Self::OpenSettings => Msg::GoToUrl(Urls::new(base_url).settings()),
+            Self::ShowBookmarks => Msg::ToggleBookmarksPanel,
+            Self::OpenAllGuides(seed_version) => {
+                Msg::GoToUrl(Urls::new(base_url).all_guides(seed_version))
+            },
+        }
+    }
+}
+
+// ------ matched commands ------
+
+/// Commands matching the palette query, the best match first.
+pub fn matched_commands(palette: &CommandPalette, model: &Model) -> Vec<Command> {
+    let query = palette.query.to_lowercase();
+    let mut scored_commands = all_commands(model)
+        .into_iter()
+        .filter_map(|command| {
+            fuzzy_score(&query, &command.title().to_lowercase())
+                .map(|score| (score, command))
+        })
+        .collect::<Vec<_>>();
+
+    // `sort_by` is stable so commands with the same score keep their order.
+    scored_commands.sort_by(|(score_a, _), (score_b, _)| score_b.cmp(score_a));
+    scored_commands
+        .into_iter()
+        .map(|(_, command)| command)
+        .take(MAX_VISIBLE_COMMANDS)
+        .collect()
+}
+
+fn all_commands(model: &Model) -> Vec<Command> {
+    let mut commands = Vec::new();
+
+    if let Page::Guide {
+        guide,
+        ..
+    } = &model.page
+    {
+        if let Some(previous_guide) = previous_guide(guide, &model.guides) {
+            commands.push(Command::GoToPreviousGuide(*previous_guide));
+        }
+        if let Some(next_guide) = next_guide(guide, &model.guides) {
+            commands.push(Command::GoToNextGuide(*next_guide));
+        }
+    }
+    commands.extend(
+        Mode::ALL
+            .iter()
+            .filter(|mode| **mode != model.mode)
+            .map(|mode| Command::SelectMode(*mode)),
+    );
+    commands.push(Command::ShowShortcuts);
+    commands.push(Command::OpenSettings);
+    commands.push(Command::ShowBookmarks);
+    commands.push(Command::OpenAllGuides(model.selected_seed_version));
+
+    commands.extend(
+        SEED_VERSIONS
+            .iter()
+            .filter(|seed_version| **seed_version != model.selected_seed_version)
+            .map(|seed_version| Command::SwitchVersion(*seed_version)),
+    );
+
+    let guides = model.guides.iter().filter(|guide| {
+        guide.seed_version == model.selected_seed_version.version()
+    });
+    commands.extend(guides.clone().map(|guide| Command::OpenGuide(*guide)));
+    commands.extend(guides.flat_map(|guide| {
+        guide
+            .headings()
+            .map(move |heading| Command::OpenHeading(*guide, heading))
+    }));
+
+    commands
+}
+
+/// Returns `Some(score)` if all `query` chars are in `text` in the same order.
+/// Consecutive chars and chars at word starts increase the score.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut score = 0;
+    let mut text_chars = text.chars();
+    let mut previous_text_char = None::<char>;
+    let mut consecutive = false;
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        loop {
+            let text_char = text_chars.next()?;
+            let at_word_start = previous_text_char
+                .map_or(true, |previous| !previous.is_alphanumeric());
+            previous_text_char = Some(text_char);
+
+            if text_char == query_char {
+                score += 1;
+                if consecutive {
+                    score += 2;
+                }
+                if at_word_start {
+                    score += 3;
+                }
+                consecutive = true;
+                break;
+            }
+            consecutive = false;
+        }
+    }
+    Some(score)
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model) -> Node<Msg> {
+    let palette = if let Some(palette) = &model.command_palette {
+        palette
+    } else {
+        return empty![];
+    };
+    let commands = matched_commands(palette, model);
+
+    div![
+        C![C.fixed, C.inset_0, C.z_50, C.flex, C.justify_center, C.px_4,],
+        style! {
+            St::BackgroundColor => "rgba(0, 0, 0, 0.5)",
+        },
+        ev(Ev::Click, |_| Msg::CloseCommandPalette),
+        div![
+            C![
+                C.w_full,
+                C.max_w_xl,
+                C.mt_24,
+                C.self_start,
+                C.bg_white,
+                C.rounded,
+                C.shadow_lg,
+                C.overflow_hidden,
+            ],
+            attrs! {
+                At::Custom("role".into()) => "dialog",
+                At::Custom("aria-label".into()) => "Command palette",
+            },
+            ev(Ev::Click, |event| event.stop_propagation()),
+            view_input(palette, model, commands.len()),
+            if commands.is_empty() {
+                div![C![C.px_4, C.py_3, C.text_gray_600,], "No results"]
+            } else {
+                ul![
+                    id!("command_palette_list"),
+                    attrs! {
+                        At::Custom("role".into()) => "listbox",
+                    },
+                    commands.iter().enumerate().map(|(index, command)| {
+                        view_command(command, index, index == palette.selected_index)
+                    })
+                ]
+            }
+        ]
+    ]
+}
+
+fn view_input(
+    palette: &CommandPalette,
+    model: &Model,
+    command_count: usize,
+) -> Node<Msg> {
+    input![
+        el_ref(&model.command_palette_input),
+        C![
+            C.w_full,
+            C.px_4,
+            C.py_3,
+            C.text_green_800,
+            C.border_b_4,
+            C.border_green_500,
+            C.focus__outline_none,
+        ],
+        attrs! {
+            At::Type => "text",
+            At::Placeholder => "Search guides, headings, versions and actions",
+            At::Value => palette.query,
+            At::Custom("aria-controls".into()) => "command_palette_list",
+            At::Custom("aria-activedescendant".into()) => command_id(palette.selected_index),
+        },
+        input_ev(Ev::Input, Msg::CommandPaletteQueryChanged),
+        keyboard_ev(Ev::KeyDown, move |event| {
+            let msg = match event.key().as_str() {
+                "ArrowUp" => Msg::SelectPreviousCommand(command_count),
+                "ArrowDown" => Msg::SelectNextCommand(command_count),
+                "Enter" => Msg::ExecuteSelectedCommand,
+                "Escape" => Msg::CloseCommandPalette,
+                _ => return None,
+            };
+            event.prevent_default();
+            Some(msg)
+        }),
+    ]
+}
+
+fn view_command(command: &Command, index: usize, selected: bool) -> Node<Msg> {
+    li![
+        id!(command_id(index)),
+        C![
+            C.flex,
+            C.justify_between,
+            C.items_center,
+            C.px_4,
+            C.py_2,
+            C.cursor_pointer,
+            C.text_green_800,
+            if selected {
+                C.bg_green_200
+            } else {
+                C.hover__bg_green_100
+            },
+        ],
+        attrs! {
+            At::Custom("role".into()) => "option",
+            At::Custom("aria-selected".into()) => selected,
+        },
+        ev(Ev::Click, move |_| Msg::ExecuteCommand(index)),
+        span![command.title()],
+        span![C![C.ml_4, C.text_xs, C.text_gray_600,], command.section()],
+    ]
+}
+
+fn command_id(index: usize) -> String {
+    format!("command_palette_item_{}", index)
+}
@@ -2,8 +2,8 @@
 #![allow(clippy::cognitive_complexity)]
 
 use crate::{
-    generated::css_classes::C, page::partial::image, Guide, Mode, Model, Msg,
-    Urls,
+    generated::css_classes::C, page::partial::image, prerender::href, Guide,
+    Mode, Model, Msg, Urls,
 };
 use seed::{prelude::*, *};
 
@@ -25,6 +25,7 @@ pub fn view(
             } else {
                 vec![C.mt_8]
             },
+            C.print_hidden,
             C.w_full,
             C.flex,
             C.justify_between,
@@ -40,9 +41,9 @@ pub fn view(
                 view_previous_guide_link(previous_guide, &model.base_url)
             }
         ),
-        // mode toggle or edit this page button
+        // mode selector or edit this page button
         if position == Position::Top {
-            view_mode_toggle(model.in_prerendering, model.mode)
+            view_mode_selector(model.mode)
         } else {
             view_edit_this_page(selected_guide.edit_url)
         },
@@ -60,47 +61,48 @@ fn view_empty_column() -> Node<Msg> {
     div![C![C.flex_1,]]
 }
 
-// ------ view mode toggle ------
+// ------ view mode selector ------
 
-fn view_mode_toggle(in_prerendering: bool, mode: Mode) -> Node<Msg> {
+fn view_mode_selector(selected_mode: Mode) -> Node<Msg> {
     div![
-        C![C.flex_1, C.flex, C.justify_center,],
+        C![C.flex_1, C.flex, C.justify_center, C.items_center,],
         div![
             C![
+                C.script_only,
                 C.flex,
-                C.items_center,
-                C.px_3,
+                C.text_xs,
                 C.text_gray_500,
                 C.border,
                 C.border_gray_400,
                 C.rounded_full,
-                IF!(not(in_prerendering) => vec![
-                    C.cursor_pointer,
-                    C.hover__underline,
-                    C.hover__text_gray_700,
-                    C.hover__border_gray_600,
-                ]),
-                IF!(in_prerendering => C.cursor_default),
+                C.overflow_hidden,
             ],
-            ev(Ev::Click, |_| Msg::ToggleMode),
-            span![
-                C![C.whitespace_no_wrap, C.flex, C.items_center,],
-                if in_prerendering {
-                    div![
-                        C![C.mr_1, C.h_4, C.w_4, C.rotate,],
-                        image::spinner_svg()
-                    ]
-                } else {
-                    empty![]
-                },
-                span![format!(
-                    "{} mode",
-                    match mode {
-                        Mode::Light => "Dark",
-                        Mode::Dark => "Light",
-                    }
-                ),]
-            ]
+            attrs! {
+                At::Custom("role".into()) => "radiogroup",
+                At::Custom("aria-label".into()) => "Color mode",
+            },
+            Mode::ALL.iter().map(|mode| {
+                let mode = *mode;
+                let selected = mode == selected_mode;
+                button![
+                    C![
+                        C.px_2,
+                        C.whitespace_no_wrap,
+                        C.focus__outline_none,
+                        IF!(selected => vec![C.bg_gray_200, C.text_gray_700]),
+                        IF!(not(selected) => vec![
+                            C.hover__underline,
+                            C.hover__text_gray_700,
+                        ]),
+                    ],
+                    attrs! {
+                        At::Custom("role".into()) => "radio",
+                        At::Custom("aria-checked".into()) => selected,
+                    },
+                    ev(Ev::Click, move |_| Msg::SelectMode(mode)),
+                    mode.label(),
+                ]
+            }),
         ]
     ]
 }
@@ -158,7 +160,7 @@ fn view_previous_guide_link(
                 C.focus__outline_none,
             ],
             attrs! {
-                At::Href => Urls::new(base_url).guide(previous_guide),
+                At::Href => href(&Urls::new(base_url).guide(previous_guide)),
             },
             view_previous_icon(),
             div![
@@ -187,7 +189,7 @@ fn view_next_guide_link(next_guide: &Guide, base_url: &Url) -> Node<Msg> {
                 C.focus__outline_none,
             ],
             attrs! {
-                At::Href => Urls::new(base_url).guide(next_guide),
+                At::Href => href(&Urls::new(base_url).guide(next_guide)),
             },
             div![
                 C![
@@ -2,30 +2,45 @@
 #![allow(clippy::cognitive_complexity)]
 
 use crate::{
-    generated::css_classes::C, page::partial::image, Guide, Model, Msg, Urls,
+    generated::css_classes::C,
+    page::partial::image,
+    prerender::href,
+    reading_progress::{self, ReadingProgress},
+    search::{self, GuideMatch, VersionFacet},
+    Guide, Model, Msg, SeedVersion, Urls,
     Visibility::Hidden,
+    SEARCH_QUERY_PARAM,
 };
 use seed::{
-    a, attrs, button, div, empty, hr, id, input, li, prelude::*, span, style,
-    ul, C, IF,
+    a, attrs, button, div, empty, form, hr, id, input, label, li, nodes, p,
+    prelude::*, span, style, ul, C, IF,
 };
 
-pub fn view(guide: &Guide, model: &Model) -> Node<Msg> {
+/// Search results are computed by the app, a static page can't show them.
+/// So the search form is submitted to the site search of DuckDuckGo
+/// when scripts are disabled, and a note below the form says so.
+const NO_SCRIPT_SEARCH_URL: &str = "https://duckduckgo.com/";
+const NO_SCRIPT_SEARCH_NOTE: &str =
+    "Without JavaScript, search opens DuckDuckGo results for seed-rs.org.";
+const SITE_DOMAIN: &str = "seed-rs.org";
+
+/// `selected_guide` is `None` on pages without a guide (e.g. search results).
+pub fn view(selected_guide: Option<&Guide>, model: &Model) -> Node<Msg> {
     div![
-        C![C.sidebar,],
-        view_guide_list_toggle(guide, model.in_prerendering),
-        view_guide_list_content(guide, model),
+        C![C.sidebar, C.print_hidden,],
+        view_guide_list_toggle(
+            selected_guide.map_or("Guides", |guide| guide.menu_title)
+        ),
+        view_guide_list_content(selected_guide, model),
     ]
 }
 
 // ------ view guide list toggle  ------
 
-fn view_guide_list_toggle(
-    selected_guide: &Guide,
-    in_prerendering: bool,
-) -> Node<Msg> {
+fn view_guide_list_toggle(title: &str) -> Node<Msg> {
     div![
         C![
+            C.script_only,
             C.inset_0,
             // lg__
             C.lg__hidden,
@@ -50,12 +65,8 @@ fn view_guide_list_toggle(
                 C.bg_green_500,
             ],
             ev(Ev::Click, |_| Msg::ToggleGuideList),
-            selected_guide.menu_title,
-            if in_prerendering {
-                div![C![C.h_6, C.w_6, C.rotate], image::spinner_svg()]
-            } else {
-                view_hamburger()
-            }
+            title,
+            view_hamburger(),
         ]
     ]
 }
@@ -70,7 +81,10 @@ fn view_hamburger() -> Node<Msg> {
 
 // ------ view guide list content ------
 
-fn view_guide_list_content(selected_guide: &Guide, model: &Model) -> Node<Msg> {
+fn view_guide_list_content(
+    selected_guide: Option<&Guide>,
+    model: &Model,
+) -> Node<Msg> {
     div![
         id!("menu_items"),
         C![
@@ -96,20 +110,46 @@ fn view_guide_list_content(selected_guide: &Guide, model: &Model) -> Node<Msg> {
             St::Top => em(7),
         },
         view_search(model),
+        view_search_facets(model),
         ul![model.guides.iter().filter_map(|guide| {
             if guide.seed_version != model.selected_seed_version.version() {
                 return None;
             }
 
-            let guide_is_selected = guide == selected_guide;
-            let guide_is_matched = model.matched_guides.contains(guide);
+            let guide_is_selected = Some(guide) == selected_guide;
+            let guide_match = model
+                .matched_guides
+                .iter()
+                .find(|guide_match| guide_match.guide == *guide);
+            let series_progress =
+                reading_progress::series(guide, &model.guides).map(|series| {
+                    model.reading_progress.series_progress(&series)
+                });
             Some(view_guide_list_item(
                 guide,
                 guide_is_selected,
-                guide_is_matched,
+                guide_match,
+                &model.search_query,
                 &model.base_url,
+                &model.reading_progress,
+                series_progress,
             ))
-        })]
+        })],
+        view_all_guides_link(model),
+        IF!(model.search_in_all_versions => view_results_in_other_versions(model)),
+    ]
+}
+
+fn view_all_guides_link(model: &Model) -> Node<Msg> {
+    div![
+        C![C.px_4, C.py_4, C.text_sm,],
+        a![
+            C![C.text_green_700, C.hover__underline, C.focus__outline_none,],
+            attrs! {
+                At::Href => href(&Urls::new(&model.base_url).all_guides(model.selected_seed_version)),
+            },
+            "All guides on one page (printable)",
+        ]
     ]
 }
 
@@ -126,11 +166,21 @@ fn view_search(model: &Model) -> Node<Msg> {
             // lg__
             C.lg__pt_0,
         ],
-        div![
+        form![
             C![
                 C.relative, C.pl_4, C.pr_4, // md__
                 C.md__pr_0,
             ],
+            // The form is submitted to the site search of a search engine
+            // only without scripts - the app handles `Submit` itself.
+            attrs! {
+                At::Action => NO_SCRIPT_SEARCH_URL,
+                At::Method => "get",
+            },
+            ev(Ev::Submit, |event| {
+                event.prevent_default();
+                Msg::SubmitSearch
+            }),
             // search icon
             div![
                 C![C.absolute,],
@@ -142,13 +192,13 @@ fn view_search(model: &Model) -> Node<Msg> {
             ],
             // search input
             input![
+                el_ref(&model.search_input),
                 C![
                     C.w_full,
                     C.bg_green_100,
                     C.text_sm,
                     C.text_green_800,
-                    IF!(not(model.in_prerendering) => C.placeholder_green_800),
-                    IF!(model.in_prerendering => C.placeholder_green_400),
+                    C.placeholder_green_800,
                     C.border_b_4,
                     C.border_green_500,
                     C.focus__outline_none,
@@ -160,26 +210,212 @@ fn view_search(model: &Model) -> Node<Msg> {
                 ],
                 attrs! {
                     At::Type => "search",
+                    At::Name => SEARCH_QUERY_PARAM,
                     At::Placeholder => "Search",
                     At::Value => model.search_query,
-                    At::Disabled => model.in_prerendering.as_at_value(),
                 },
                 input_ev(Ev::Input, Msg::SearchQueryChanged),
             ],
+            input![attrs! {
+                At::Type => "hidden",
+                At::Name => "sites",
+                At::Value => SITE_DOMAIN,
+            }],
+            p![
+                C![C.no_script_only, C.mt_1, C.text_xs, C.text_gray_600,],
+                NO_SCRIPT_SEARCH_NOTE,
+            ],
+        ]
+    ]
+}
+
+// ------ view search facets ------
+
+fn view_search_facets(model: &Model) -> Node<Msg> {
+    if model.search_query.is_empty() {
+        return empty![];
+    }
+    let facets = search::version_facets(&model.matched_guides);
+    div![
+        C![C.px_4, C.mb_4, C.text_sm, C.text_green_800,],
+        div![
+            C![C.flex, C.flex_wrap, C.items_center,],
+            span![
+                C![C.mr_2,],
+                format!("{} results:", model.matched_guides.len())
+            ],
+            facets.iter().map(|facet| {
+                view_search_facet(facet, model.selected_seed_version)
+            }),
+        ],
+        label![
+            C![C.flex, C.items_center, C.mt_2, C.cursor_pointer,],
+            input![
+                C![C.mr_2,],
+                attrs! {
+                    At::Type => "checkbox",
+                    At::Checked => model.search_in_all_versions.as_at_value(),
+                },
+                ev(Ev::Change, |_| Msg::ToggleSearchInAllVersions),
+            ],
+            "Include other versions",
+        ],
+        a![
+            C![
+                C.block,
+                C.mt_2,
+                C.text_blue_500,
+                C.hover__text_blue_700,
+                C.hover__underline,
+            ],
+            attrs! {
+                At::Href => href(&Urls::new(&model.base_url).search(&model.search_query, None, 1)),
+            },
+            ev(Ev::Click, |_| Msg::HideGuideList),
+            "All results",
+        ],
+    ]
+}
+
+fn view_search_facet(
+    facet: &VersionFacet,
+    selected_seed_version: SeedVersion,
+) -> Node<Msg> {
+    let selected = facet.seed_version == selected_seed_version;
+    span![
+        C![
+            C.mr_2,
+            C.px_2,
+            C.rounded_full,
+            if selected {
+                vec![C.bg_green_200, C.font_bold]
+            } else {
+                vec![C.bg_gray_200]
+            },
+            IF!(facet.count == 0 => C.text_gray_500),
+        ],
+        format!("{} ({})", facet.seed_version.version(), facet.count),
+    ]
+}
+
+// ------ view results in other versions ------
+
+fn view_results_in_other_versions(model: &Model) -> Vec<Node<Msg>> {
+    search::version_facets(&model.matched_guides)
+        .into_iter()
+        .filter(|facet| {
+            facet.seed_version != model.selected_seed_version && facet.count > 0
+        })
+        .flat_map(|facet| {
+            let seed_version = facet.seed_version;
+            nodes![
+                div![
+                    C![
+                        C.mt_4,
+                        C.px_4,
+                        C.py_1,
+                        C.text_sm,
+                        C.font_bold,
+                        C.text_green_800,
+                        C.border_t,
+                        C.border_green_300,
+                    ],
+                    format!(
+                        "Seed {} ({})",
+                        seed_version.version(),
+                        facet.count
+                    ),
+                ],
+                ul![model
+                    .matched_guides
+                    .iter()
+                    .filter(|guide_match| {
+                        guide_match.guide.seed_version == seed_version.version()
+                    })
+                    .map(|guide_match| view_other_version_item(
+                        guide_match,
+                        seed_version,
+                        &model.search_query,
+                        &model.base_url
+                    ))],
+            ]
+        })
+        .collect()
+}
+
+fn view_other_version_item(
+    guide_match: &GuideMatch,
+    seed_version: SeedVersion,
+    search_query: &str,
+    base_url: &Url,
+) -> Node<Msg> {
+    let guide = &guide_match.guide;
+    li![
+        C![if guide_match.fuzzy {
+            C.bg_green_100
+        } else {
+            C.bg_green_200
+        },],
+        a![
+            C![
+                C.flex,
+                C.items_center,
+                C.justify_between,
+                C.py_1,
+                C.pl_4,
+                C.pr_2,
+                C.text_green_800,
+                C.hover__text_green_500,
+                C.focus__outline_none,
+            ],
+            attrs! {
+                At::Href => if guide_match.fuzzy {
+                    href(&Urls::new(base_url).guide(guide))
+                } else {
+                    href(&Urls::new(base_url).guide_with_highlight(guide, search_query))
+                },
+                At::Title => format!("This guide belongs to Seed {}", seed_version.version()),
+            },
+            ev(Ev::Click, |_| Msg::HideGuideList),
+            span![
+                guide.menu_title,
+                IF!(guide_match.fuzzy => view_fuzzy_mark()),
+            ],
+            span![
+                C![
+                    C.ml_2,
+                    C.px_2,
+                    C.text_xs,
+                    C.rounded_full,
+                    C.bg_blue_100,
+                    C.text_blue_800,
+                    C.whitespace_no_wrap,
+                ],
+                seed_version.version(),
+            ],
         ]
     ]
 }
 
+// ------ view guide list item ------
+
+/// `series_progress` is `(completed, total)` if the guide starts a series.
 fn view_guide_list_item(
     guide: &Guide,
     active: bool,
-    matched: bool,
+    guide_match: Option<&GuideMatch>,
+    search_query: &str,
     base_url: &Url,
+    reading_progress: &ReadingProgress,
+    series_progress: Option<(usize, usize)>,
 ) -> Node<Msg> {
+    let matched = guide_match.is_some();
+    let fuzzy = guide_match.map_or(false, |guide_match| guide_match.fuzzy);
     li![
         C![
             IF!(not(matched) => C.hover__bg_green_100),
-            IF!(matched => C.bg_green_200),
+            IF!(matched && not(fuzzy) => C.bg_green_200),
+            IF!(fuzzy => C.bg_green_100),
             // md__
             C.md__my_0,
             // lg__
@@ -190,6 +426,7 @@ fn view_guide_list_item(
         } else {
             empty![]
         },
+        series_progress.map(view_series_progress),
         a![
             C![
                 C.block,
@@ -210,7 +447,11 @@ fn view_guide_list_item(
                 },
             ],
             attrs! {
-                At::Href => Urls::new(base_url).guide(guide),
+                At::Href => if matched && not(fuzzy) {
+                    href(&Urls::new(base_url).guide_with_highlight(guide, search_query))
+                } else {
+                    href(&Urls::new(base_url).guide(guide))
+                },
             },
             ev(Ev::Click, |_| Msg::HideGuideList),
             span![
@@ -221,7 +462,70 @@ fn view_guide_list_item(
                     IF!(active => C.font_bold),
                 ],
                 guide.menu_title,
+                IF!(fuzzy => view_fuzzy_mark()),
+                view_reading_mark(guide, reading_progress),
             ]
         ]
     ]
 }
+
+fn view_reading_mark(
+    guide: &Guide,
+    reading_progress: &ReadingProgress,
+) -> Node<Msg> {
+    if reading_progress.is_completed(guide) {
+        span![
+            C![C.ml_1, C.text_green_500,],
+            attrs! {
+                At::Title => "Completed",
+            },
+            "\u{2713}",
+        ]
+    } else if reading_progress.is_visited(guide) {
+        span![
+            C![C.ml_1, C.text_gray_500,],
+            attrs! {
+                At::Title => "Visited",
+            },
+            "\u{2022}",
+        ]
+    } else {
+        empty![]
+    }
+}
+
+fn view_series_progress((completed, total): (usize, usize)) -> Node<Msg> {
+    div![
+        C![C.flex, C.items_center, C.px_4, C.pt_2, C.text_xs, C.text_gray_600,],
+        attrs! {
+            At::Title => format!("{} of {} guides completed", completed, total),
+        },
+        div![
+            C![
+                C.flex_1,
+                C.h_1,
+                C.mr_2,
+                C.bg_gray_200,
+                C.rounded_full,
+                C.overflow_hidden,
+            ],
+            div![
+                C![C.h_full, C.bg_green_500,],
+                style! {
+                    St::Width => format!("{}%", completed * 100 / total),
+                },
+            ],
+        ],
+        format!("{}/{}", completed, total),
+    ]
+}
+
+fn view_fuzzy_mark() -> Node<Msg> {
+    span![
+        C![C.ml_1, C.text_xs, C.text_gray_600,],
+        attrs! {
+            At::Title => "Similar words found",
+        },
+        "(similar)",
+    ]
+}
@@ -2,17 +2,19 @@
 #![allow(clippy::cognitive_complexity)]
 
 use crate::{
-    generated::css_classes::C,
-    page::partial::{blender, image},
-    Model, Msg, Page, Urls,
+    changelog, generated::css_classes::C, offline::OfflineStatus,
+    page::partial::image, prerender::href, Model, Msg, Page, Urls,
     Visibility::Hidden,
 };
-use seed::{a, attrs, button, div, id, li, nav, prelude::*, span, ul, C, IF};
+use seed::{
+    a, attrs, button, div, empty, id, li, nav, prelude::*, span, ul, C, IF,
+};
 
 pub fn view(model: &Model) -> Node<Msg> {
     nav![
         id!("header"),
         C![
+            C.print_hidden,
             C.fixed,
             C.w_full,
             C.z_30,
@@ -22,7 +24,6 @@ pub fn view(model: &Model) -> Node<Msg> {
             // lg__
             C.lg__shadow_none,
         ],
-        blender::view_for_header(model.mode),
         // container
         div![
             C![
@@ -39,13 +40,9 @@ pub fn view(model: &Model) -> Node<Msg> {
                 C.pb_2,
             ],
             view_container_with_border(),
-            view_guide_list_toggle(
-                model.page,
-                model.in_prerendering,
-                &model.base_url
-            ),
+            view_guide_list_toggle(model.page, &model.base_url),
             view_logo(&model.base_url),
-            view_menu_toggle(model.in_prerendering),
+            view_menu_toggle(),
             view_menu_content(model),
         ]
     ]
@@ -69,16 +66,22 @@ fn view_container_with_border() -> Node<Msg> {
 
 // ------ view guide list toggle  ------
 
-fn view_guide_list_toggle(
-    page: Page,
-    in_prerendering: bool,
-    base_url: &Url,
-) -> Node<Msg> {
-    let page_is_guide = matches!(page, Page::Guide { .. });
+fn view_guide_list_toggle(page: Page, base_url: &Url) -> Node<Msg> {
+    let page_has_guide_list = matches!(
+        page,
+        Page::Guide { .. }
+            | Page::Search { .. }
+            | Page::Settings
+            | Page::AllGuides { .. }
+            | Page::Changelog { .. }
+            | Page::Glossary
+    );
 
     let toggle = button![
         id!("view_guide_list_toggle"),
         C![
+            // The guide list is always visible without scripts.
+            IF!(page_has_guide_list => C.script_only),
             C.flex,
             C.items_center,
             C.px_3,
@@ -96,11 +99,7 @@ fn view_guide_list_toggle(
         ],
         ev(Ev::Click, |_| Msg::ScrollToTop),
         ev(Ev::Click, |_| Msg::ToggleGuideList),
-        if in_prerendering {
-            div![C![C.h_6, C.w_6, C.rotate], image::spinner_svg()]
-        } else {
-            span!["Guides",]
-        }
+        span!["Guides",]
     ];
 
     div![
@@ -111,12 +110,12 @@ fn view_guide_list_toggle(
             // lg__
             C.lg__hidden
         ],
-        if page_is_guide {
+        if page_has_guide_list {
             toggle
         } else {
             a![
                 attrs! {
-                    At::Href => Urls::new(base_url).home()
+                    At::Href => href(&Urls::new(base_url).home())
                 },
                 toggle,
             ]
@@ -146,7 +145,7 @@ fn view_logo(base_url: &Url) -> Node<Msg> {
                 C.lg__w_32,
             ],
             attrs! {
-                At::Href => Urls::new(base_url).home()
+                At::Href => href(&Urls::new(base_url).home())
             },
             image::seed_logo_svg(),
         ]
@@ -155,9 +154,10 @@ fn view_logo(base_url: &Url) -> Node<Msg> {
 
 // ------ view menu  ------
 
-fn view_menu_toggle(in_prerendering: bool) -> Node<Msg> {
+fn view_menu_toggle() -> Node<Msg> {
     div![
         C![
+            C.script_only,
             C.relative,
             C.pr_4,
             C.flex,
@@ -183,11 +183,7 @@ fn view_menu_toggle(in_prerendering: bool) -> Node<Msg> {
                 C.hover__underline,
             ],
             ev(Ev::Click, |_| Msg::ToggleMenu),
-            if in_prerendering {
-                div![C![C.h_6, C.w_6, C.rotate], image::spinner_svg()]
-            } else {
-                span!["Menu",]
-            }
+            span!["Menu",]
         ]
     ]
 }
@@ -213,12 +209,13 @@ fn view_menu_content(model: &Model) -> Node<Msg> {
             C.lg__w_auto,
             C.lg__mt_0,
         ],
-        view_links(),
+        view_links(&model.base_url),
+        view_offline_status(model.offline_status, &model.base_url),
         view_github_mark(),
     ]
 }
 
-fn view_links() -> Node<Msg> {
+fn view_links(base_url: &Url) -> Node<Msg> {
     ul![
         C![
             C.justify_end,
@@ -237,6 +234,39 @@ fn view_links() -> Node<Msg> {
         view_link("Docs.rs", "https://docs.rs/seed/latest/seed"),
         view_link("Crates.io", "https://crates.io/crates/seed"),
         view_link("Awesome List", "https://github.com/seed-rs/awesome-seed-rs"),
+        view_link(
+            "Changelog",
+            &href(&Urls::new(base_url).changelog(changelog::Filter::default()))
+        ),
+        view_link("Glossary", &href(&Urls::new(base_url).glossary())),
+        view_link("Settings", &href(&Urls::new(base_url).settings())),
+        view_bookmarks_button(),
+    ]
+}
+
+fn view_bookmarks_button() -> Node<Msg> {
+    li![
+        C![
+            C.script_only,
+            C.mr_3,
+            C.py_2,
+            // lg__
+            C.lg__py_0,
+        ],
+        button![
+            C![
+                C.inline_block,
+                C.py_2,
+                C.px_4,
+                C.text_blue_500,
+                C.hover__text_blue_700,
+                C.hover__underline,
+                C.font_bold,
+                C.focus__outline_none,
+            ],
+            ev(Ev::Click, |_| Msg::ToggleBookmarksPanel),
+            "Bookmarks"
+        ]
     ]
 }
 
@@ -265,11 +295,42 @@ fn view_link(title: &str, link: &str) -> Node<Msg> {
     ]
 }
 
+fn view_offline_status(status: OfflineStatus, base_url: &Url) -> Node<Msg> {
+    let text = match status {
+        OfflineStatus::Unsupported | OfflineStatus::NothingSaved => {
+            return empty![]
+        },
+        OfflineStatus::Caching {
+            cached,
+            total,
+        } => format!("Saving offline {}/{}", cached, total),
+        OfflineStatus::Available => "\u{2713} Offline".to_owned(),
+        OfflineStatus::Failed => "Offline incomplete".to_owned(),
+    };
+    a![
+        C![
+            C.mr_3,
+            C.py_2,
+            C.px_4,
+            C.text_xs,
+            C.text_gray_600,
+            C.hover__underline,
+            C.focus__outline_none,
+        ],
+        attrs! {
+            At::Href => href(&Urls::new(base_url).settings()),
+            At::Title => status.label(),
+        },
+        text,
+    ]
+}
+
 fn view_github_mark() -> Node<Msg> {
     a![
         C![
             C.mt_4, C.mb_8, C.mr_8, // lg__
             C.lg__mx_3, C.lg__my_0,
+            C.text_gray_900,
         ],
         attrs! {
             At::Href => "https://github.com/seed-rs/seed",
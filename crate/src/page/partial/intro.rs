@@ -2,8 +2,8 @@
 #![allow(clippy::cognitive_complexity)]
 
 use crate::{
-    generated::css_classes::C, page::partial::image, Guide, Msg, SeedVersion,
-    Urls, DEFAULT_GUIDE_SLUG, SEED_VERSIONS,
+    generated::css_classes::C, page::partial::image, prerender::href, Guide,
+    Msg, SeedVersion, Urls, DEFAULT_GUIDE_SLUG, SEED_VERSIONS,
 };
 use seed::{prelude::*, *};
 
@@ -45,7 +45,7 @@ fn view_logo(base_url: &Url) -> Node<Msg> {
         a![
             C![C.w_48, C.focus__outline_none, C.mt_8, C.lg__w_64,],
             attrs! {
-                At::Href => Urls::new(base_url).home()
+                At::Href => href(&Urls::new(base_url).home())
             },
             image::seed_logo_svg(),
         ],
@@ -71,34 +71,58 @@ fn view_description_and_versions(
                             && guide.seed_version == version.version()
                     })
                     .unwrap();
-                a![
-                    C![
-                        C.flex,
-                        C.justify_between,
-                        C.items_center,
-                        C.rounded_full,
-                        C.p_2,
-                        C.cursor_pointer,
-                        C.focus__outline_none,
-                        if version == selected_seed_version {
-                            vec![C.text_blue_800, C.bg_green_200]
-                        } else {
-                            vec![C.text_blue_600]
+                div![
+                    C![C.flex, C.items_center,],
+                    a![
+                        C![
+                            C.flex,
+                            C.flex_grow,
+                            C.justify_between,
+                            C.items_center,
+                            C.rounded_full,
+                            C.p_2,
+                            C.cursor_pointer,
+                            C.focus__outline_none,
+                            if version == selected_seed_version {
+                                vec![C.text_blue_800, C.bg_green_200]
+                            } else {
+                                vec![C.text_blue_600]
+                            },
+                            C.hover__text_blue_800,
+                            C.hover__bg_green_200,
+                        ],
+                        attrs! {
+                            At::Href => href(&Urls::new(base_url).guide(default_guide))
                         },
-                        C.hover__text_blue_800,
-                        C.hover__bg_green_200,
+                        span![C![C.font_bold, C.px_2,], version.version()],
+                        span![C![C.text_sm, C.px_2,], version.date(),],
                     ],
-                    attrs! {
-                        At::Href => Urls::new(base_url).guide(default_guide)
-                    },
-                    span![C![C.font_bold, C.px_2,], version.version()],
-                    span![C![C.text_sm, C.px_2,], version.date(),],
+                    view_epub_link(version),
                 ]
             })
         ]
     ]
 }
 
+fn view_epub_link(version: SeedVersion) -> Node<Msg> {
+    a![
+        C![
+            C.ml_2,
+            C.px_2,
+            C.text_xs,
+            C.text_blue_600,
+            C.hover__text_blue_800,
+            C.hover__underline,
+        ],
+        attrs! {
+            At::Href => version.epub_path(),
+            At::Download => "",
+            At::Title => format!("Seed {} guides as an e-book", version.version()),
+        },
+        "EPUB",
+    ]
+}
+
 fn view_description() -> Node<Msg> {
     h2![
         C![
@@ -1,6 +1,10 @@
-pub mod blender;
+pub mod annotation_editor;
+pub mod bookmarks_panel;
+pub mod command_palette;
 pub mod content_control_panel;
 pub mod guide_list;
 pub mod header;
 pub mod image;
 pub mod intro;
+pub mod shortcut_help;
+pub mod update_prompt;
@@ -0,0 +1,137 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    generated::css_classes::C, shortcut::Action, Model, Msg, ShortcutHelp,
+};
+use seed::{prelude::*, *};
+
+pub fn view(model: &Model) -> Node<Msg> {
+    let help = if let Some(help) = &model.shortcut_help {
+        help
+    } else {
+        return empty![];
+    };
+
+    div![
+        C![C.fixed, C.inset_0, C.z_50, C.flex, C.justify_center, C.px_4,],
+        style! {
+            St::BackgroundColor => "rgba(0, 0, 0, 0.5)",
+        },
+        ev(Ev::Click, |_| Msg::ToggleShortcutHelp),
+        div![
+            C![
+                C.w_full,
+                C.max_w_2xl,
+                C.mt_16,
+                C.mb_16,
+                C.self_start,
+                C.p_6,
+                C.bg_white,
+                C.rounded,
+                C.shadow_lg,
+                C.overflow_y_auto,
+            ],
+            style! {
+                St::MaxHeight => "calc(100vh - 8rem)",
+            },
+            attrs! {
+                At::Custom("role".into()) => "dialog",
+                At::Custom("aria-label".into()) => "Keyboard shortcuts",
+            },
+            ev(Ev::Click, |event| event.stop_propagation()),
+            div![
+                C![C.flex, C.justify_between, C.items_center, C.mb_4,],
+                h2![C![C.text_2xl, C.font_bold,], "Keyboard shortcuts"],
+                button![
+                    C![
+                        C.text_green_500,
+                        C.hover__text_green_700,
+                        C.hover__underline,
+                        C.focus__outline_none,
+                    ],
+                    ev(Ev::Click, |_| Msg::ToggleShortcutHelp),
+                    "Close",
+                ],
+            ],
+            p![
+                C![C.mb_4, C.text_sm, C.text_gray_600,],
+                "Type a new binding and press Enter. Use e.g. ",
+                kbd!["Ctrl+Shift+K"],
+                ", ",
+                kbd!["g g"],
+                " for a sequence, or ",
+                kbd!["Ctrl+K, Meta+K"],
+                " for alternatives.",
+            ],
+            table![
+                C![C.w_full, C.text_sm,],
+                tbody![Action::ALL
+                    .iter()
+                    .map(|action| view_shortcut(*action, help, model))],
+            ],
+        ]
+    ]
+}
+
+fn view_shortcut(
+    action: Action,
+    help: &ShortcutHelp,
+    model: &Model,
+) -> Node<Msg> {
+    let overridden = model.shortcuts.overrides().contains_key(&action);
+    let invalid = help.invalid_binding == Some(action);
+    tr![
+        C![C.border_b, C.border_gray_200,],
+        td![C![C.py_2, C.pr_4,], action.description()],
+        td![
+            C![C.py_2,],
+            input![
+                C![
+                    C.w_full,
+                    C.px_2,
+                    C.py_1,
+                    C.font_mono,
+                    C.border,
+                    C.rounded,
+                    C.focus__outline_none,
+                    if invalid {
+                        C.border_red_500
+                    } else {
+                        C.border_gray_400
+                    },
+                ],
+                attrs! {
+                    At::Type => "text",
+                    At::Value => model.shortcuts.bindings_text(action),
+                    At::Custom("aria-label".into()) => format!("Shortcut for: {}", action.description()),
+                    At::Custom("aria-invalid".into()) => invalid,
+                },
+                ev(Ev::Change, move |event| {
+                    let binding = event
+                        .target()
+                        .and_then(|target| seed::util::get_value(&target).ok())
+                        .unwrap_or_default();
+                    Msg::ChangeShortcut(action, binding)
+                }),
+            ],
+            IF!(invalid => div![
+                C![C.mt_1, C.text_xs, C.text_red_500,],
+                "Invalid binding",
+            ]),
+        ],
+        td![
+            C![C.py_2, C.pl_4, C.text_right,],
+            IF!(overridden => button![
+                C![
+                    C.text_blue_500,
+                    C.hover__text_blue_700,
+                    C.hover__underline,
+                    C.focus__outline_none,
+                ],
+                ev(Ev::Click, move |_| Msg::ResetShortcut(action)),
+                "Reset",
+            ]),
+        ],
+    ]
+}
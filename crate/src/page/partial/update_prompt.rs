@@ -0,0 +1,42 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{generated::css_classes::C, Model, Msg};
+use seed::{prelude::*, *};
+
+pub fn view(model: &Model) -> Node<Msg> {
+    if !model.update_available {
+        return empty![];
+    }
+    div![
+        C![
+            C.fixed,
+            C.bottom_0,
+            C.right_0,
+            C.z_40,
+            C.m_4,
+            C.p_4,
+            C.flex,
+            C.items_center,
+            C.text_sm,
+            C.text_white,
+            C.bg_blue_500,
+            C.rounded,
+            C.shadow_lg,
+        ],
+        attrs! {
+            At::Custom("role".into()) => "alert",
+        },
+        span![C![C.mr_4,], "A new version of the docs is available."],
+        button![
+            C![C.mr_2, C.font_bold, C.hover__underline, C.focus__outline_none,],
+            ev(Ev::Click, |_| Msg::ReloadToUpdate),
+            "Reload",
+        ],
+        button![
+            C![C.hover__underline, C.focus__outline_none,],
+            ev(Ev::Click, |_| Msg::DismissUpdate),
+            "Later",
+        ],
+    ]
+}
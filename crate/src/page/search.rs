@@ -0,0 +1,214 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    generated::css_classes::C,
+    page::partial::guide_list,
+    prerender::href,
+    search::{self, SearchResult},
+    Model, Msg, SeedVersion, Urls, SEED_VERSIONS,
+};
+use seed::{prelude::*, *};
+
+const RESULTS_PER_PAGE: usize = 10;
+
+pub fn view(
+    seed_version: Option<SeedVersion>,
+    page_number: usize,
+    model: &Model,
+) -> Node<Msg> {
+    div![
+        C![
+            C.mx_auto,
+            C.px_2,
+            C.mt_16,
+            // lg__
+            C.lg__mt_0,
+            C.container,
+        ],
+        guide_list::view(None, model),
+        view_content(seed_version, page_number, model),
+    ]
+}
+
+fn view_content(
+    seed_version: Option<SeedVersion>,
+    page_number: usize,
+    model: &Model,
+) -> Node<Msg> {
+    let results = &model.search_results;
+    let page_count = (results.len() + RESULTS_PER_PAGE - 1) / RESULTS_PER_PAGE;
+
+    div![
+        C![
+            C.bg_white,
+            // lg__
+            C.lg__pt_24,
+            C.content_container,
+        ],
+        el_ref(&model.guide_content_el),
+        h1![
+            C![C.text_3xl, C.font_bold, C.mt_6, C.mb_4, C.lg__mt_0,],
+            if model.search_query.is_empty() {
+                "Search".to_owned()
+            } else {
+                format!("Search results for \"{}\"", model.search_query)
+            }
+        ],
+        view_version_filter(seed_version, model),
+        if results.is_empty() {
+            p![C![C.my_8, C.text_gray_600,], "No guides found."]
+        } else {
+            ol![results
+                .iter()
+                .skip((page_number - 1) * RESULTS_PER_PAGE)
+                .take(RESULTS_PER_PAGE)
+                .map(|result| view_result(
+                    result,
+                    &model.search_query,
+                    &model.base_url
+                ))]
+        },
+        IF!(page_count > 1 => view_pagination(seed_version, page_number, page_count, model)),
+    ]
+}
+
+// ------ view version filter ------
+
+fn view_version_filter(
+    selected_seed_version: Option<SeedVersion>,
+    model: &Model,
+) -> Node<Msg> {
+    let facets = search::version_facets(&model.matched_guides);
+    let view_filter_link = |title: String, seed_version: Option<SeedVersion>| {
+        let active = seed_version == selected_seed_version;
+        a![
+            C![
+                C.mr_2,
+                C.mb_2,
+                C.px_3,
+                C.py_1,
+                C.text_sm,
+                C.rounded_full,
+                C.focus__outline_none,
+                if active {
+                    vec![C.bg_green_200, C.text_green_900, C.font_bold]
+                } else {
+                    vec![C.bg_gray_200, C.text_gray_700, C.hover__bg_green_100]
+                },
+            ],
+            attrs! {
+                At::Href => href(&Urls::new(&model.base_url).search(&model.search_query, seed_version, 1)),
+            },
+            title,
+        ]
+    };
+
+    div![
+        C![C.flex, C.flex_wrap, C.mb_6,],
+        view_filter_link(
+            format!("All versions ({})", model.matched_guides.len()),
+            None
+        ),
+        SEED_VERSIONS.iter().map(|seed_version| {
+            let count = facets
+                .iter()
+                .find(|facet| facet.seed_version == *seed_version)
+                .map_or(0, |facet| facet.count);
+            view_filter_link(
+                format!("{} ({})", seed_version.version(), count),
+                Some(*seed_version),
+            )
+        }),
+    ]
+}
+
+// ------ view result ------
+
+fn view_result(
+    result: &SearchResult,
+    search_query: &str,
+    base_url: &Url,
+) -> Node<Msg> {
+    li![
+        C![C.mb_6,],
+        a![
+            C![
+                C.flex,
+                C.items_center,
+                C.text_lg,
+                C.font_bold,
+                C.text_green_800,
+                C.hover__text_green_500,
+                C.hover__underline,
+            ],
+            attrs! {
+                At::Href => if result.fuzzy {
+                    href(&Urls::new(base_url).guide_with_highlight(&result.guide, &result.snippet.matched))
+                } else {
+                    href(&Urls::new(base_url).guide_with_highlight(&result.guide, search_query))
+                },
+            },
+            span![result.guide.menu_title],
+            span![
+                C![
+                    C.ml_2,
+                    C.px_2,
+                    C.text_xs,
+                    C.font_normal,
+                    C.rounded_full,
+                    C.bg_blue_100,
+                    C.text_blue_800,
+                ],
+                result.guide.seed_version,
+            ],
+            IF!(result.fuzzy => span![
+                C![C.ml_2, C.text_xs, C.font_normal, C.text_gray_600,],
+                attrs! {
+                    At::Title => "The guide contains only words similar to the query",
+                },
+                "similar match",
+            ]),
+        ],
+        p![
+            C![C.mt_1, C.text_sm, C.text_gray_700,],
+            &result.snippet.before,
+            mark![&result.snippet.matched],
+            &result.snippet.after,
+        ]
+    ]
+}
+
+// ------ view pagination ------
+
+fn view_pagination(
+    seed_version: Option<SeedVersion>,
+    page_number: usize,
+    page_count: usize,
+    model: &Model,
+) -> Node<Msg> {
+    let view_page_link = |title: &str, target_page_number: usize| {
+        a![
+            C![
+                C.px_3,
+                C.text_green_500,
+                C.hover__text_green_700,
+                C.hover__underline,
+            ],
+            attrs! {
+                At::Href => href(&Urls::new(&model.base_url).search(&model.search_query, seed_version, target_page_number)),
+            },
+            title,
+        ]
+    };
+
+    nav![
+        C![C.flex, C.justify_center, C.items_center, C.my_8, C.text_sm,],
+        IF!(page_number > 1 => view_page_link("Previous", page_number - 1)),
+        span![
+            C![C.px_3, C.text_gray_600,],
+            format!("Page {} of {}", page_number, page_count),
+        ],
+        IF!(page_number < page_count => view_page_link("Next", page_number + 1)),
+    ]
+}
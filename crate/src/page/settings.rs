@@ -0,0 +1,223 @@
+// Clippy complains about `cognitive_complexity` for simple functions with macros.
+#![allow(clippy::cognitive_complexity)]
+
+use crate::{
+    config::{CodeTheme, ContentWidth, FontSize, Settings},
+    generated::css_classes::C,
+    offline::OfflineStatus,
+    page::partial::guide_list,
+    Mode, Model, Msg, SEED_VERSIONS,
+};
+use seed::{prelude::*, *};
+
+pub fn view(model: &Model) -> Node<Msg> {
+    div![
+        C![
+            C.mx_auto,
+            C.px_2,
+            C.mt_16,
+            // lg__
+            C.lg__mt_0,
+            C.container,
+        ],
+        guide_list::view(None, model),
+        view_content(model),
+    ]
+}
+
+fn view_content(model: &Model) -> Node<Msg> {
+    let settings = &model.settings;
+    div![
+        C![
+            C.bg_white,
+            // lg__
+            C.lg__pt_24,
+            C.content_container,
+        ],
+        el_ref(&model.guide_content_el),
+        h1![
+            C![C.text_3xl, C.font_bold, C.mt_6, C.mb_4, C.lg__mt_0,],
+            "Settings"
+        ],
+        p![
+            C![C.mb_6, C.text_sm, C.text_gray_600,],
+            "Settings are saved in this browser.",
+        ],
+        view_options(
+            "Color mode",
+            Mode::ALL,
+            model.mode,
+            Mode::label,
+            Msg::SelectMode
+        ),
+        view_options(
+            "Font size",
+            FontSize::ALL,
+            settings.font_size,
+            FontSize::label,
+            Msg::SelectFontSize
+        ),
+        view_options(
+            "Content width",
+            ContentWidth::ALL,
+            settings.content_width,
+            ContentWidth::label,
+            Msg::SelectContentWidth
+        ),
+        view_options(
+            "Code theme",
+            CodeTheme::ALL,
+            settings.code_theme,
+            CodeTheme::label,
+            Msg::SelectCodeTheme
+        ),
+        view_reduced_motion(settings),
+        view_offline(settings, model.offline_status),
+        view_section(
+            "Keyboard shortcuts",
+            view_button("Edit shortcuts", || Msg::ToggleShortcutHelp),
+        ),
+        div![
+            C![C.mt_8, C.mb_16,],
+            view_button("Reset settings", || Msg::ResetSettings),
+        ],
+    ]
+}
+
+// ------ view section ------
+
+fn view_section(title: &str, content: Node<Msg>) -> Node<Msg> {
+    section![
+        C![C.mb_6,],
+        h2![C![C.mb_2, C.font_bold, C.text_green_800,], title],
+        content,
+    ]
+}
+
+// ------ view options ------
+
+fn view_options<T: Copy + PartialEq + 'static>(
+    title: &str,
+    options: &[T],
+    selected_option: T,
+    label: fn(T) -> &'static str,
+    to_msg: fn(T) -> Msg,
+) -> Node<Msg> {
+    view_section(
+        title,
+        div![
+            C![C.flex, C.flex_wrap,],
+            attrs! {
+                At::Custom("role".into()) => "radiogroup",
+                At::Custom("aria-label".into()) => title,
+            },
+            options.iter().map(|option| {
+                let option = *option;
+                let selected = option == selected_option;
+                button![
+                    C![
+                        C.mr_2,
+                        C.mb_2,
+                        C.px_3,
+                        C.py_1,
+                        C.text_sm,
+                        C.rounded_full,
+                        C.focus__outline_none,
+                        if selected {
+                            vec![C.bg_green_200, C.text_green_900, C.font_bold]
+                        } else {
+                            vec![
+                                C.bg_gray_200,
+                                C.text_gray_700,
+                                C.hover__bg_green_100,
+                            ]
+                        },
+                    ],
+                    attrs! {
+                        At::Custom("role".into()) => "radio",
+                        At::Custom("aria-checked".into()) => selected,
+                    },
+                    ev(Ev::Click, move |_| to_msg(option)),
+                    label(option),
+                ]
+            }),
+        ],
+    )
+}
+
+// ------ view reduced motion ------
+
+fn view_reduced_motion(settings: &Settings) -> Node<Msg> {
+    view_section(
+        "Motion",
+        label![
+            C![C.flex, C.items_center, C.text_sm, C.cursor_pointer,],
+            input![
+                C![C.mr_2,],
+                attrs! {
+                    At::Type => "checkbox",
+                    At::Checked => settings.reduced_motion.as_at_value(),
+                },
+                ev(Ev::Change, |_| Msg::ToggleReducedMotion),
+            ],
+            "Reduce motion (disable animations)",
+        ],
+    )
+}
+
+// ------ view offline ------
+
+fn view_offline(settings: &Settings, status: OfflineStatus) -> Node<Msg> {
+    let supported = status != OfflineStatus::Unsupported;
+    view_section(
+        "Offline reading",
+        div![
+            p![C![C.mb_2, C.text_sm, C.text_gray_600,], status.label()],
+            SEED_VERSIONS.iter().map(|seed_version| {
+                let seed_version = *seed_version;
+                let version = seed_version.version();
+                label![
+                    C![
+                        C.flex,
+                        C.items_center,
+                        C.text_sm,
+                        C.cursor_pointer,
+                    ],
+                    input![
+                        C![C.mr_2,],
+                        attrs! {
+                            At::Type => "checkbox",
+                            At::Checked => settings.offline_versions.contains(version).as_at_value(),
+                            At::Disabled => (!supported).as_at_value(),
+                        },
+                        ev(Ev::Change, move |_| {
+                            Msg::ToggleOfflineVersion(seed_version)
+                        }),
+                    ],
+                    "Save guides for Seed ",
+                    version,
+                ]
+            }),
+        ],
+    )
+}
+
+// ------ view button ------
+
+fn view_button(title: &str, on_click: fn() -> Msg) -> Node<Msg> {
+    button![
+        C![
+            C.px_3,
+            C.py_1,
+            C.text_sm,
+            C.border,
+            C.rounded,
+            C.text_green_700,
+            C.border_green_500,
+            C.hover__bg_green_100,
+            C.focus__outline_none,
+        ],
+        ev(Ev::Click, move |_| on_click()),
+        title,
+    ]
+}
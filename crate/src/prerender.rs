@@ -0,0 +1,284 @@
+//! Rendering of pages to static HTML on the host (see `src/bin/prerender.rs`).
+//!
+//! Seed parses raw HTML and formats `Url`s with browser APIs,
+//! so views use `raw_html` and `href` to stay renderable outside the browser.
+//!
+//! Seed takes over the prerendered DOM on start and patches it with the first
+//! render, so views mustn't depend on being prerendered - the DOM would be
+//! replaced instead. Controls that need the app have the `script-only` class
+//! to be hidden when scripts are disabled.
+
+use crate::{
+    code_block, config::Config, guide, seo, view, Model, Msg, Page,
+    CHANGELOG_PATH_PART, GLOSSARY_PATH_PART,
+};
+use seed::prelude::*;
+
+/// A custom element wrapping raw HTML in the host build.
+/// It's replaced by its content in `to_html`.
+#[cfg(not(target_arch = "wasm32"))]
+const RAW_HTML_TAG: &str = "prerender-raw-html";
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "source", "track", "wbr",
+];
+
+// ------ view helpers ------
+
+/// `raw!` replacement.
+#[cfg(target_arch = "wasm32")]
+pub fn raw_html(html: &str) -> Vec<Node<Msg>> {
+    seed::raw!(html)
+}
+
+/// `raw!` replacement.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn raw_html(html: &str) -> Vec<Node<Msg>> {
+    let mut el = El::empty(Tag::from(RAW_HTML_TAG));
+    el.children.push(Node::new_text(with_code_fallbacks(html)));
+    vec![Node::Element(el)]
+}
+
+/// Adds plain `<pre><code>` content to `<code-block>` elements,
+/// so the code is readable before `code-block` is defined or without scripts.
+/// The app removes it on start because its `<code-block>`s are empty.
+#[cfg(not(target_arch = "wasm32"))]
+fn with_code_fallbacks(html: &str) -> String {
+    const END: &str = "></code-block>";
+
+    let mut with_fallbacks = String::with_capacity(html.len() * 2);
+    let mut rest = html;
+    while let Some(start) = rest.find(code_block::START) {
+        let end = start + rest[start..].find(END).expect("code-block end");
+        let code = code_block::attribute(&rest[start..end], "code")
+            .unwrap_or_default();
+
+        with_fallbacks.push_str(&rest[..=end]);
+        with_fallbacks.push_str(&format!(
+            "<pre><code>{}</code></pre></code-block>",
+            escape(&code, false)
+        ));
+        rest = &rest[end + END.len()..];
+    }
+    with_fallbacks.push_str(rest);
+    with_fallbacks
+}
+
+/// `Url` formatted for `href` and `action` attributes.
+#[cfg(target_arch = "wasm32")]
+pub fn href(url: &Url) -> String {
+    url.to_string()
+}
+
+/// `Url` formatted for `href` and `action` attributes.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn href(url: &Url) -> String {
+    let mut href = format!(
+        "/{}",
+        url.path()
+            .iter()
+            .map(|part| percent_encode(part, false))
+            .collect::<Vec<_>>()
+            .join("/")
+    );
+    let search = url
+        .search()
+        .iter()
+        .flat_map(|(key, values)| {
+            values.iter().map(move |value| {
+                format!(
+                    "{}={}",
+                    percent_encode(key, true),
+                    percent_encode(value, true)
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    if !search.is_empty() {
+        href.push('?');
+        href.push_str(&search.join("&"));
+    }
+    if let Some(hash) = url.hash() {
+        href.push('#');
+        href.push_str(hash);
+    }
+    href
+}
+
+/// Encodes like Seed in browsers - path parts with `encodeURIComponent`
+/// and the search with `URLSearchParams`.
+#[cfg(not(target_arch = "wasm32"))]
+fn percent_encode(text: &str, in_search: bool) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b' ' if in_search => "+".to_owned(),
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'*'
+            | b'-'
+            | b'.'
+            | b'_' => char::from(byte).to_string(),
+            b'!' | b'\'' | b'(' | b')' | b'~' if !in_search => {
+                char::from(byte).to_string()
+            },
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+// ------ pages ------
+
+pub struct PrerenderedPage {
+    pub title: String,
+    /// Metadata elements appended to `<head>`.
+    pub head: String,
+    /// Content of the app's root element.
+    pub html: String,
+}
+
+/// Path parts of all prerendered pages except 404 -
+/// the home page, the changelog, the glossary and guides.
+pub fn paths() -> Vec<Vec<String>> {
+    let guide_paths = guide::guides().into_iter().map(|guide| {
+        vec![guide.seed_version.to_owned(), guide.slug.to_owned()]
+    });
+    vec![
+        Vec::new(),
+        vec![CHANGELOG_PATH_PART.to_owned()],
+        vec![GLOSSARY_PATH_PART.to_owned()],
+    ]
+    .into_iter()
+    .chain(guide_paths)
+    .collect()
+}
+
+/// Renders the page with the default config and without stored data.
+/// Unknown paths are rendered as the 404 page.
+pub fn render(path: &[String]) -> PrerenderedPage {
+    let url = Url::new().set_path(path);
+    render_model(Model::new(url, Config::default(), false))
+}
+
+pub fn render_not_found() -> PrerenderedPage {
+    let mut model = Model::new(Url::new(), Config::default(), false);
+    model.page = Page::NotFound;
+    render_model(model)
+}
+
+fn render_model(model: Model) -> PrerenderedPage {
+    PrerenderedPage {
+        title: model.page.title(),
+        head: seo::head_html(model.page, &model.guides),
+        html: view(&model).iter().map(to_html).collect(),
+    }
+}
+
+// ------ HTML ------
+
+fn to_html(node: &Node<Msg>) -> String {
+    match node {
+        Node::Element(el) => el_to_html(el),
+        Node::Text(text) => escape(&text.text, false),
+        Node::Empty | Node::NoChange => String::new(),
+    }
+}
+
+fn el_to_html(el: &El<Msg>) -> String {
+    let tag = el.tag.as_str();
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if tag == RAW_HTML_TAG {
+            return el
+                .children
+                .iter()
+                .filter_map(|child| match child {
+                    Node::Text(text) => Some(text.text.as_ref()),
+                    _ => None,
+                })
+                .collect();
+        }
+    }
+
+    let mut html = format!("<{}", tag);
+    for (name, value) in &el.attrs.vals {
+        match value {
+            AtValue::Ignored => (),
+            AtValue::None => html.push_str(&format!(" {}", name.as_str())),
+            AtValue::Some(value) => html.push_str(&format!(
+                r#" {}="{}""#,
+                name.as_str(),
+                escape(value, true)
+            )),
+        }
+    }
+    let style = el.style.to_string();
+    if !style.is_empty() {
+        html.push_str(&format!(r#" style="{}""#, escape(&style, true)));
+    }
+    html.push('>');
+    if VOID_ELEMENTS.contains(&tag) {
+        return html;
+    }
+    html.extend(el.children.iter().map(to_html));
+    html.push_str(&format!("</{}>", tag));
+    html
+}
+
+pub fn escape(text: &str, in_attribute: bool) -> String {
+    let text =
+        text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    if in_attribute {
+        text.replace('"', "&quot;")
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Expected values are outputs of Seed's `Url::to_string` in browsers.
+
+    #[test]
+    fn href_encodes_search_like_url_search_params() {
+        let url = Url::new()
+            .set_path(&["search"])
+            .set_search(UrlSearch::new(vec![("q", vec!["a b&c=d+e"])]));
+        assert_eq!(href(&url), "/search?q=a+b%26c%3Dd%2Be");
+
+        let url = Url::new()
+            .set_search(UrlSearch::new(vec![("k y", vec!["čaj*~!"])]));
+        assert_eq!(href(&url), "/?k+y=%C4%8Daj*%7E%21");
+    }
+
+    #[test]
+    fn href_encodes_path_parts_like_encode_uri_component() {
+        let url = Url::new().set_path(&["0.8.0", "čaj & (more)!"]);
+        assert_eq!(href(&url), "/0.8.0/%C4%8Daj%20%26%20(more)!");
+
+        let url = Url::new().set_path(&["a/b?c#d", "x=y;z~'*"]);
+        assert_eq!(href(&url), "/a%2Fb%3Fc%23d/x%3Dy%3Bz~'*");
+    }
+
+    #[test]
+    fn href_appends_hash() {
+        let url = Url::new().set_path(&["0.8.0", "routing"]).set_hash("urls");
+        assert_eq!(href(&url), "/0.8.0/routing#urls");
+    }
+
+    #[test]
+    fn escape_text_and_attributes() {
+        let text = r#"<a href="x">Tom & "Jerry"</a>"#;
+        assert_eq!(
+            escape(text, false),
+            r#"&lt;a href="x"&gt;Tom &amp; "Jerry"&lt;/a&gt;"#
+        );
+        assert_eq!(
+            escape(text, true),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &quot;Jerry&quot;&lt;/a&gt;"
+        );
+    }
+}
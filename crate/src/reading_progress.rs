@@ -0,0 +1,207 @@
+use crate::{
+    guide::{self, Guide},
+    storage::{self, Migration},
+    DEFAULT_GUIDE_SLUG,
+};
+use seed::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const STORAGE_KEY: &str = "seed_reading_progress";
+/// Increment it on every `ReadingProgress` schema change and add a migration.
+const CURRENT_VERSION: u64 = 2;
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+/// The guide is completed when the reader scrolls this close to its end.
+const SCROLL_END_TOLERANCE_PX: f64 = 50.;
+
+// ------ ReadingProgress ------
+
+/// Visited and completed guides. Keys are `{seed_version}/{slug}`.
+#[derive(Serialize, Deserialize)]
+pub struct ReadingProgress {
+    version: u64,
+    visited: BTreeSet<String>,
+    completed: BTreeSet<String>,
+    /// Slugs of the last visited guides by Seed version.
+    last_visited: BTreeMap<String, String>,
+}
+
+impl Default for ReadingProgress {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            visited: BTreeSet::new(),
+            completed: BTreeSet::new(),
+            last_visited: BTreeMap::new(),
+        }
+    }
+}
+
+impl ReadingProgress {
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY, CURRENT_VERSION, MIGRATIONS)
+    }
+
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    pub fn is_visited(&self, guide: &Guide) -> bool {
+        self.visited.contains(&guide_key(guide))
+    }
+
+    pub fn is_completed(&self, guide: &Guide) -> bool {
+        self.completed.contains(&guide_key(guide))
+    }
+
+    pub fn visit(&mut self, guide: &Guide) {
+        // The about guide is the home page, there is nothing to continue.
+        if guide.slug != DEFAULT_GUIDE_SLUG {
+            self.last_visited
+                .insert(guide.seed_version.to_owned(), guide.slug.to_owned());
+        }
+        self.visited.insert(guide_key(guide));
+    }
+
+    /// Returns `false` if the guide has been already completed.
+    pub fn complete(&mut self, guide: &Guide) -> bool {
+        self.completed.insert(guide_key(guide))
+    }
+
+    pub fn uncomplete(&mut self, guide: &Guide) {
+        self.completed.remove(&guide_key(guide));
+    }
+
+    /// The last visited guide of the given Seed version.
+    pub fn last_visited<'a>(
+        &self,
+        guides: &'a [Guide],
+        seed_version: &str,
+    ) -> Option<&'a Guide> {
+        let slug = self.last_visited.get(seed_version)?;
+        guide::find(guides, seed_version, slug)
+    }
+
+    /// Returns `(completed, total)` guide counts in the series.
+    pub fn series_progress(&self, series: &[Guide]) -> (usize, usize) {
+        let completed =
+            series.iter().filter(|guide| self.is_completed(guide)).count();
+        (completed, series.len())
+    }
+}
+
+fn guide_key(guide: &Guide) -> String {
+    format!("{}/{}", guide.seed_version, guide.slug)
+}
+
+// ------ migrations ------
+
+/// Adds `version` and replaces the key of the last visited guide
+/// with the map of the last visited slugs by Seed version.
+fn migrate_v1_to_v2(progress: &mut Map<String, Value>) {
+    let last_visited = progress
+        .get("last_visited")
+        .and_then(Value::as_str)
+        .and_then(|key| key.split_once('/'))
+        .map(|(seed_version, slug)| (seed_version.to_owned(), slug.into()))
+        .into_iter()
+        .collect::<Map<_, _>>();
+    progress.insert("last_visited".to_owned(), Value::Object(last_visited));
+}
+
+// ------ series ------
+
+/// Guides from `first_guide` to the next menu divider (e.g. TodoMVC).
+/// Returns `None` if `first_guide` doesn't start a series with multiple guides.
+pub fn series(first_guide: &Guide, guides: &[Guide]) -> Option<Vec<Guide>> {
+    if !first_guide.prepend_menu_divider {
+        return None;
+    }
+    let series = guides
+        .iter()
+        .filter(|guide| guide.seed_version == first_guide.seed_version)
+        .skip_while(|guide| *guide != first_guide)
+        .enumerate()
+        .take_while(|(index, guide)| *index == 0 || !guide.prepend_menu_divider)
+        .map(|(_, guide)| *guide)
+        .collect::<Vec<_>>();
+    if series.len() > 1 {
+        Some(series)
+    } else {
+        None
+    }
+}
+
+// ------ scroll end ------
+
+pub fn is_scrolled_to_end() -> bool {
+    let document_element = match document().document_element() {
+        Some(document_element) => document_element,
+        None => return false,
+    };
+    let scroll_y = window().scroll_y().unwrap_or_default();
+    let scroll_end = f64::from(document_element.scroll_height())
+        - f64::from(document_element.client_height());
+    scroll_y >= scroll_end - SCROLL_END_TOLERANCE_PX
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn from_json(progress: Value) -> Result<ReadingProgress, String> {
+        storage::from_json(progress, CURRENT_VERSION, MIGRATIONS)
+    }
+
+    #[test]
+    fn migrates_v1_to_current_version() {
+        let progress = from_json(json!({
+            "visited": ["0.7.0/init", "0.8.0/model"],
+            "completed": ["0.7.0/init"],
+            "last_visited": "0.8.0/model",
+        }))
+        .unwrap();
+        let guides = guide::guides();
+
+        assert_eq!(progress.version, CURRENT_VERSION);
+        assert_eq!(progress.visited.len(), 2);
+        assert_eq!(
+            progress.last_visited(&guides, "0.8.0").map(|guide| guide.slug),
+            Some("model")
+        );
+        assert!(progress.last_visited(&guides, "0.7.0").is_none());
+    }
+
+    #[test]
+    fn migrates_v1_without_last_visited() {
+        let progress = from_json(json!({
+            "visited": [],
+            "completed": [],
+            "last_visited": null,
+        }))
+        .unwrap();
+        assert!(progress.last_visited.is_empty());
+    }
+
+    #[test]
+    fn remembers_last_visited_guide_per_seed_version() {
+        let guides = guide::guides();
+        let mut progress = ReadingProgress::default();
+        for (seed_version, slug) in
+            &[("0.7.0", "init"), ("0.8.0", "model"), ("0.8.0", "about")]
+        {
+            progress.visit(guide::find(&guides, seed_version, slug).unwrap());
+        }
+
+        let last_visited_slug = |seed_version| {
+            progress
+                .last_visited(&guides, seed_version)
+                .map(|guide| guide.slug)
+        };
+        assert_eq!(last_visited_slug("0.7.0"), Some("init"));
+        assert_eq!(last_visited_slug("0.8.0"), Some("model"));
+        assert_eq!(last_visited_slug("0.6.0"), None);
+    }
+}
@@ -0,0 +1,590 @@
+use crate::{
+    code_block, generated::css_classes::C, guide::Guide, SeedVersion,
+    SEED_VERSIONS,
+};
+use std::cmp::Ordering;
+
+/// Lowercase words and code identifiers from all guides, one per line.
+/// (see `build.rs`)
+const VOCABULARY: &str = include_str!("../generated_guides/vocabulary.txt");
+
+// ------ GuideMatch ------
+
+#[derive(Clone, Copy)]
+pub struct GuideMatch {
+    pub guide: Guide,
+    /// `true` if the guide contains only words similar to the query.
+    pub fuzzy: bool,
+}
+
+// ------ VersionFacet ------
+
+pub struct VersionFacet {
+    pub seed_version: SeedVersion,
+    pub count: usize,
+}
+
+// ------ Query ------
+
+/// Lowercased query with fuzzy candidates for each of its terms.
+struct Query {
+    text: String,
+    term_candidates: Vec<Vec<String>>,
+}
+
+impl Query {
+    fn new(query: &str) -> Self {
+        let text = query.to_lowercase();
+        let term_candidates = text
+            .split_whitespace()
+            .map(|term| {
+                let mut candidates = vec![term.to_owned()];
+                candidates.extend(similar_words(term).map(ToOwned::to_owned));
+                candidates
+            })
+            .collect();
+        Self {
+            text,
+            term_candidates,
+        }
+    }
+
+    fn matches_exactly(&self, guide: &Guide) -> bool {
+        guide.lowercase_text.contains(&self.text)
+    }
+
+    /// Returns found candidates if every query term has at least one.
+    fn fuzzy_matches<'a>(&'a self, guide: &Guide) -> Option<Vec<&'a str>> {
+        let mut found_candidates = Vec::new();
+        for candidates in &self.term_candidates {
+            let found = candidates
+                .iter()
+                .filter(|candidate| guide.lowercase_text.contains(*candidate))
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            if found.is_empty() {
+                return None;
+            }
+            found_candidates.extend(found);
+        }
+        Some(found_candidates)
+    }
+}
+
+// ------ search ------
+
+/// Returns guides from all Seed versions that contain the query
+/// or words similar to the query terms.
+pub fn search(guides: &[Guide], query: &str) -> Vec<GuideMatch> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let query = Query::new(query);
+    guides
+        .iter()
+        .filter_map(|guide| {
+            if query.matches_exactly(guide) {
+                Some(GuideMatch {
+                    guide: *guide,
+                    fuzzy: false,
+                })
+            } else {
+                query.fuzzy_matches(guide).map(|_| GuideMatch {
+                    guide: *guide,
+                    fuzzy: true,
+                })
+            }
+        })
+        .collect()
+}
+
+/// Counts matched guides per Seed version, in `SEED_VERSIONS` order.
+pub fn version_facets(matched_guides: &[GuideMatch]) -> Vec<VersionFacet> {
+    SEED_VERSIONS
+        .iter()
+        .map(|seed_version| VersionFacet {
+            seed_version: *seed_version,
+            count: matched_guides
+                .iter()
+                .filter(|guide_match| {
+                    guide_match.guide.seed_version == seed_version.version()
+                })
+                .count(),
+        })
+        .collect()
+}
+
+// ------ fuzzy matching ------
+
+fn similar_words(term: &str) -> impl Iterator<Item = &'static str> + '_ {
+    let term_len = term.chars().count();
+    let max_distance = max_edit_distance(term_len);
+
+    VOCABULARY.lines().filter(move |word| {
+        max_distance > 0
+            && *word != term
+            && word.chars().count().saturating_sub(term_len) <= max_distance
+            && term_len.saturating_sub(word.chars().count()) <= max_distance
+            && edit_distance(term, word) <= max_distance
+    })
+}
+
+/// Short terms have to match exactly, otherwise we'd match nearly everything.
+const fn max_edit_distance(term_len: usize) -> usize {
+    match term_len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// [Optimal string alignment distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Optimal_string_alignment_distance)
+/// - Levenshtein distance where transposition of two adjacent chars counts as one edit.
+#[allow(clippy::needless_range_loop)]
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+// ------ SearchResult ------
+
+pub struct SearchResult {
+    pub guide: Guide,
+    pub score: usize,
+    pub fuzzy: bool,
+    pub snippet: Snippet,
+}
+
+pub struct Snippet {
+    pub before: String,
+    pub matched: String,
+    pub after: String,
+}
+
+/// Chars displayed around the first match in `Snippet`.
+const SNIPPET_CONTEXT_CHARS: usize = 80;
+/// Score bonus for guides with the query in their menu title.
+const TITLE_MATCH_SCORE: usize = 10;
+
+/// Returns matched guides ordered by score, the highest first.
+/// Exact matches are always ranked above fuzzy ones.
+/// Only guides of `seed_version` are returned if it's set.
+pub fn ranked_results(
+    guides: &[Guide],
+    query: &str,
+    seed_version: Option<SeedVersion>,
+) -> Vec<SearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let query = Query::new(query);
+    let mut results = guides
+        .iter()
+        .filter(|guide| {
+            seed_version.map_or(true, |seed_version| {
+                guide.seed_version == seed_version.version()
+            })
+        })
+        .filter_map(|guide| {
+            if query.matches_exactly(guide) {
+                Some(scored_result(guide, &[query.text.as_str()], false))
+            } else {
+                query
+                    .fuzzy_matches(guide)
+                    .map(|found| scored_result(guide, &found, true))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // `sort_by` is stable so guides with the same score keep their order.
+    results.sort_by(|a, b| match (a.fuzzy, b.fuzzy) {
+        (false, true) => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        _ => b.score.cmp(&a.score),
+    });
+    results
+}
+
+fn scored_result(guide: &Guide, found: &[&str], fuzzy: bool) -> SearchResult {
+    let mut score = 0;
+    for text in found {
+        score += guide.lowercase_text.matches(text).count();
+        if guide.menu_title.to_lowercase().contains(text) {
+            score += TITLE_MATCH_SCORE;
+        }
+    }
+
+    let (match_start, match_len) = found
+        .iter()
+        .find_map(|text| {
+            guide.lowercase_text.find(text).map(|start| (start, text.len()))
+        })
+        .unwrap_or_default();
+
+    SearchResult {
+        guide: *guide,
+        score,
+        fuzzy,
+        snippet: snippet(guide, match_start, match_len),
+    }
+}
+
+fn snippet(guide: &Guide, match_start: usize, match_len: usize) -> Snippet {
+    // Offsets are valid in `text` only if lowercasing hasn't changed its length.
+    let text = if guide.text.len() == guide.lowercase_text.len()
+        && guide.text.is_char_boundary(match_start)
+        && guide.text.is_char_boundary(match_start + match_len)
+    {
+        guide.text
+    } else {
+        guide.lowercase_text
+    };
+    let match_end = match_start + match_len;
+
+    let before_start = text[..match_start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_CHARS - 1)
+        .map_or(0, |(index, _)| index);
+    let after_end = text[match_end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map_or(text.len(), |(index, _)| match_end + index);
+
+    let ellipsis = |add: bool| if add { "…" } else { "" };
+    Snippet {
+        before: format!(
+            "{}{}",
+            ellipsis(before_start > 0),
+            &text[before_start..match_start]
+        ),
+        matched: text[match_start..match_end].to_owned(),
+        after: format!(
+            "{}{}",
+            &text[match_end..after_end],
+            ellipsis(after_end < text.len())
+        ),
+    }
+}
+
+// ------ highlight in HTML ------
+
+pub const SEARCH_MATCH_ID_PREFIX: &str = "search-match-";
+
+pub enum HtmlPart<'a> {
+    Tag(&'a str),
+    Text(&'a str),
+}
+
+/// Counts occurrences of the query in the text content of the HTML
+/// and in the code of `<code-block>`s.
+pub fn count_matches_in_html(html: &str, query: &str) -> usize {
+    let query = query.to_lowercase();
+    html_parts(html)
+        .into_iter()
+        .map(|part| match part {
+            HtmlPart::Tag(tag) => code_block::attribute(tag, "code")
+                .map_or(0, |code| match_ranges(&code, &query).len()),
+            HtmlPart::Text(text) => text_match_ranges(text, &query).len(),
+        })
+        .sum()
+}
+
+/// Wraps occurrences of the query in the text content of the HTML
+/// with `<mark id="search-match-{index}">`.
+/// Occurrences in the code of `<code-block>`s are passed in its attribute
+/// `marks` as `[[start, end, "id", "class"]]` because the code is highlighted
+/// by the element itself.
+pub fn mark_matches_in_html(
+    html: &str,
+    query: &str,
+    active_match: usize,
+) -> String {
+    let query = query.to_lowercase();
+    let mut marked_html = String::with_capacity(html.len());
+    let mut match_index = 0;
+    let mut next_mark = || {
+        let id = format!("{}{}", SEARCH_MATCH_ID_PREFIX, match_index);
+        let classes = if match_index == active_match {
+            format!("{} {}", C.search_match, C.search_match_active)
+        } else {
+            C.search_match.to_owned()
+        };
+        match_index += 1;
+        (id, classes)
+    };
+
+    for part in html_parts(html) {
+        match part {
+            HtmlPart::Tag(tag) => match code_block::attribute(tag, "code") {
+                Some(code) => {
+                    let marks = match_ranges(&code, &query)
+                        .into_iter()
+                        .map(|(start, end)| {
+                            let (id, classes) = next_mark();
+                            format!(
+                                r#"[{},{},"{}","{}"]"#,
+                                code[..start].encode_utf16().count(),
+                                code[..end].encode_utf16().count(),
+                                id,
+                                classes
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    if marks.is_empty() {
+                        marked_html.push_str(tag);
+                    } else {
+                        marked_html.push_str(&format!(
+                            r#"{} marks="[{}]">"#,
+                            tag.trim_end_matches('>'),
+                            marks.join(",").replace('"', "&quot;")
+                        ));
+                    }
+                },
+                None => marked_html.push_str(tag),
+            },
+            HtmlPart::Text(text) => {
+                let mut position = 0;
+                for (start, end) in text_match_ranges(text, &query) {
+                    marked_html.push_str(&text[position..start]);
+                    let (id, classes) = next_mark();
+                    marked_html.push_str(&format!(
+                        r#"<mark id="{}" class="{}">{}</mark>"#,
+                        id,
+                        classes,
+                        &text[start..end]
+                    ));
+                    position = end;
+                }
+                marked_html.push_str(&text[position..]);
+            },
+        }
+    }
+    marked_html
+}
+
+/// Byte ranges of the lowercase `query` in the HTML `text`.
+/// Characters are compared after decoding character references,
+/// so e.g. the query `amp` doesn't match inside `&amp;`
+/// and a match never splits a reference.
+fn text_match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let (decoded_text, offsets) = decode_html(text);
+    match_ranges(&decoded_text, query)
+        .into_iter()
+        .map(|(start, end)| (offsets[start], offsets[end]))
+        .collect()
+}
+
+/// Byte ranges of the lowercase `query` in `text`, compared case-insensitively.
+fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let lowercase_text = text.to_lowercase();
+    // Offsets are valid in `text` only if lowercasing hasn't changed its length.
+    if lowercase_text.len() != text.len() {
+        return Vec::new();
+    }
+    lowercase_text
+        .match_indices(query)
+        .map(|(start, matched)| (start, start + matched.len()))
+        .filter(|(start, end)| {
+            text.is_char_boundary(*start) && text.is_char_boundary(*end)
+        })
+        .collect()
+}
+
+/// Decodes character references like `&amp;` or `&#39;`.
+/// Returns the decoded text and the offset in `html` for each byte
+/// of the decoded text and for its end.
+fn decode_html(html: &str) -> (String, Vec<usize>) {
+    let mut decoded = String::with_capacity(html.len());
+    let mut offsets = Vec::with_capacity(html.len() + 1);
+    let mut position = 0;
+
+    while let Some(character) = html[position..].chars().next() {
+        let (character, len) = if character == '&' {
+            character_reference(&html[position..])
+                .unwrap_or((character, character.len_utf8()))
+        } else {
+            (character, character.len_utf8())
+        };
+        decoded.push(character);
+        offsets.resize(decoded.len(), position);
+        position += len;
+    }
+    offsets.push(html.len());
+    (decoded, offsets)
+}
+
+/// The character and the length of the reference at the start of `html`.
+fn character_reference(html: &str) -> Option<(char, usize)> {
+    // The longest supported reference is `&#x10FFFF;`.
+    let end = html.get(..11).unwrap_or(html).find(';')?;
+    let character = match &html[1..end] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        name => {
+            let code = name.strip_prefix('#')?;
+            let code =
+                match code.strip_prefix('x').or_else(|| code.strip_prefix('X'))
+                {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+            char::from_u32(code)?
+        },
+    };
+    Some((character, end + 1))
+}
+
+/// Splits HTML into tags and text between them.
+/// Quoted attribute values may contain `>` (e.g. `code` in `<code-block>`).
+/// Hidden glossary tooltips (see `build/glossary.rs`) are returned as one tag,
+/// so their text isn't searched, marked or annotated.
+pub fn html_parts(html: &str) -> Vec<HtmlPart<'_>> {
+    const TOOLTIP_START: &str = r#"<span class="glossary-tooltip""#;
+    const TOOLTIP_END: &str = "</span>";
+
+    let bytes = html.as_bytes();
+    let mut parts = Vec::new();
+    let mut position = 0;
+
+    while position < bytes.len() {
+        let tooltip_end = if html[position..].starts_with(TOOLTIP_START) {
+            html[position..].find(TOOLTIP_END)
+        } else {
+            None
+        };
+        if let Some(tooltip_end) = tooltip_end {
+            let end = position + tooltip_end + TOOLTIP_END.len();
+            parts.push(HtmlPart::Tag(&html[position..end]));
+            position = end;
+        } else if bytes[position] == b'<' {
+            let mut quote = None;
+            let mut end = position + 1;
+            while end < bytes.len() {
+                match (quote, bytes[end]) {
+                    (None, b'>') => break,
+                    (None, byte @ b'"') | (None, byte @ b'\'') => {
+                        quote = Some(byte)
+                    },
+                    (Some(quote_byte), byte) if byte == quote_byte => {
+                        quote = None
+                    },
+                    _ => (),
+                }
+                end += 1;
+            }
+            let end = (end + 1).min(bytes.len());
+            parts.push(HtmlPart::Tag(&html[position..end]));
+            position = end;
+        } else {
+            let end = html[position..]
+                .find('<')
+                .map_or(html.len(), |index| position + index);
+            parts.push(HtmlPart::Text(&html[position..end]));
+            position = end;
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guide(slug: &'static str, text: &'static str) -> Guide {
+        Guide {
+            seed_version: "0.8.0",
+            slug,
+            menu_title: slug,
+            html: text,
+            lowercase_text: text,
+            text,
+            outline: "",
+            description: "",
+            og_image_path: "",
+            prepend_menu_divider: false,
+            edit_url: "",
+        }
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("routing", "routing"), 0);
+        assert_eq!(edit_distance("routng", "routing"), 1);
+        assert_eq!(edit_distance("rotuing", "routing"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "view"), 4);
+        assert_eq!(edit_distance("übung", "ubung"), 1);
+    }
+
+    #[test]
+    fn max_edit_distance_grows_with_term_length() {
+        assert_eq!(max_edit_distance(3), 0);
+        assert_eq!(max_edit_distance(4), 1);
+        assert_eq!(max_edit_distance(7), 1);
+        assert_eq!(max_edit_distance(8), 2);
+    }
+
+    #[test]
+    fn ranked_results_put_exact_matches_before_fuzzy_ones() {
+        let guides = [
+            guide("fuzzy", "routing routing routing"),
+            guide("exact", "a routng typo"),
+            guide("none", "view"),
+        ];
+
+        let results = ranked_results(&guides, "routng", None);
+
+        let slugs = results
+            .iter()
+            .map(|result| (result.guide.slug, result.fuzzy))
+            .collect::<Vec<_>>();
+        assert_eq!(slugs, vec![("exact", false), ("fuzzy", true)]);
+        assert!(results[1].score > results[0].score);
+    }
+
+    #[test]
+    fn ranked_results_filter_by_seed_version() {
+        let guides = [guide("routing", "routing")];
+
+        assert_eq!(
+            ranked_results(&guides, "routing", Some(SeedVersion::V0_8_0))
+                .len(),
+            1
+        );
+        assert!(ranked_results(&guides, "routing", Some(SeedVersion::V0_7_0))
+            .is_empty());
+        assert!(ranked_results(&guides, " ", None).is_empty());
+    }
+}
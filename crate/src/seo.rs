@@ -0,0 +1,248 @@
+//! Metadata in `<head>` for search engines and link previews.
+//! The app updates it on route changes and the prerenderer writes it
+//! statically, so crawlers without scripts get the same tags.
+
+use crate::{
+    guide::Guide,
+    prerender::{self, href},
+    Page, Urls, SITE_URL, TITLE_SUFFIX,
+};
+use seed::{prelude::*, *};
+use serde_json::json;
+
+const SITE_DESCRIPTION: &str =
+    "Rust framework for creating fast and reliable web apps";
+const CHANGELOG_DESCRIPTION: &str =
+    "Seed releases with breaking changes, features and fixes";
+const GLOSSARY_DESCRIPTION: &str =
+    "Seed terms like Model, Msg, Orders and subscriptions explained";
+/// Guides have their own images rendered by `build.rs`.
+const DEFAULT_IMAGE_PATH: &str = "/static/images/og_image.jpg";
+/// The size of guide images (see `build/og_image.rs`).
+const GUIDE_IMAGE_SIZE: (u32, u32) = (1200, 630);
+const TWITTER_CARD: &str = "summary_large_image";
+const JSON_LD_TYPE: &str = "application/ld+json";
+
+struct MetaTag {
+    /// `name`, `property` or `itemprop`.
+    attribute: &'static str,
+    key: &'static str,
+    /// `None` removes the tag.
+    content: Option<String>,
+}
+
+impl MetaTag {
+    fn new(
+        attribute: &'static str,
+        key: &'static str,
+        content: impl Into<Option<String>>,
+    ) -> Self {
+        Self {
+            attribute,
+            key,
+            content: content.into(),
+        }
+    }
+}
+
+fn description(page: Page) -> &'static str {
+    match page {
+        Page::Guide {
+            guide,
+            ..
+        } if !guide.description.is_empty() => guide.description,
+        Page::Changelog {
+            ..
+        } => CHANGELOG_DESCRIPTION,
+        Page::Glossary => GLOSSARY_DESCRIPTION,
+        _ => SITE_DESCRIPTION,
+    }
+}
+
+fn meta_tags(page: Page, canonical_url: Option<&str>) -> Vec<MetaTag> {
+    let title = page.title();
+    let description = description(page).to_owned();
+    let (og_type, image_path, image_size) = match page {
+        Page::Guide {
+            guide,
+            ..
+        } => ("article", guide.og_image_path, Some(GUIDE_IMAGE_SIZE)),
+        _ => ("website", DEFAULT_IMAGE_PATH, None),
+    };
+    let image_url = absolute_url(image_path);
+    vec![
+        MetaTag::new("name", "description", description.clone()),
+        MetaTag::new("name", "image", image_url.clone()),
+        MetaTag::new("itemprop", "name", title.clone()),
+        MetaTag::new("itemprop", "description", description.clone()),
+        MetaTag::new("itemprop", "image", image_url.clone()),
+        MetaTag::new("property", "og:title", title.clone()),
+        MetaTag::new("property", "og:description", description.clone()),
+        MetaTag::new("property", "og:type", og_type.to_owned()),
+        MetaTag::new("property", "og:image", image_url.clone()),
+        MetaTag::new(
+            "property",
+            "og:image:width",
+            image_size.map(|(width, _)| width.to_string()),
+        ),
+        MetaTag::new(
+            "property",
+            "og:image:height",
+            image_size.map(|(_, height)| height.to_string()),
+        ),
+        MetaTag::new(
+            "property",
+            "og:url",
+            canonical_url.map(ToOwned::to_owned),
+        ),
+        MetaTag::new("name", "twitter:card", TWITTER_CARD.to_owned()),
+        MetaTag::new("name", "twitter:title", title),
+        MetaTag::new("name", "twitter:description", description),
+        MetaTag::new("name", "twitter:image", image_url),
+    ]
+}
+
+/// `TechArticle` structured data of guides.
+fn json_ld(page: Page) -> Option<String> {
+    let guide = match page {
+        Page::Guide {
+            guide,
+            ..
+        } => guide,
+        _ => return None,
+    };
+    let json_ld = json!({
+        "@context": "https://schema.org",
+        "@type": "TechArticle",
+        "headline": guide.menu_title,
+        "description": description(page),
+        "url": absolute_url(&href(&Urls::new(Url::new()).guide(&guide))),
+        "inLanguage": "en",
+        "version": guide.seed_version,
+        "about": {
+            "@type": "SoftwareSourceCode",
+            "name": "Seed",
+            "version": guide.seed_version,
+            "programmingLanguage": "Rust",
+            "codeRepository": "https://github.com/seed-rs/seed",
+        },
+        "isPartOf": {
+            "@type": "WebSite",
+            "name": TITLE_SUFFIX,
+            "url": absolute_url("/"),
+        },
+    });
+    // `</script>` in a string mustn't close the script element.
+    Some(json_ld.to_string().replace("</", r"<\/"))
+}
+
+fn absolute_url(path: &str) -> String {
+    format!("{}{}", SITE_URL, path)
+}
+
+// ------ DOM ------
+
+/// Sets the title, the canonical link, meta tags and JSON-LD.
+pub fn update_head(page: Page, guides: &[Guide]) {
+    document().set_title(&page.title());
+    let head = document()
+        .query_selector("head")
+        .expect("query head")
+        .expect("get head");
+
+    let canonical_url = page.canonical_url(guides);
+    let canonical_link = [("rel", "canonical")];
+    match &canonical_url {
+        Some(url) => find_or_append(&head, "link", &canonical_link)
+            .set_attribute("href", url)
+            .expect("set canonical href"),
+        None => remove(&head, "link", &canonical_link),
+    }
+
+    for tag in meta_tags(page, canonical_url.as_deref()) {
+        let attributes = [(tag.attribute, tag.key)];
+        match tag.content {
+            Some(content) => find_or_append(&head, "meta", &attributes)
+                .set_attribute("content", &content)
+                .expect("set meta content"),
+            None => remove(&head, "meta", &attributes),
+        }
+    }
+
+    let json_ld_script = [("type", JSON_LD_TYPE)];
+    match json_ld(page) {
+        Some(json_ld) => find_or_append(&head, "script", &json_ld_script)
+            .set_text_content(Some(&json_ld)),
+        None => remove(&head, "script", &json_ld_script),
+    }
+}
+
+fn selector(tag_name: &str, attributes: &[(&str, &str)]) -> String {
+    attributes.iter().fold(tag_name.to_owned(), |selector, (name, value)| {
+        format!(r#"{}[{}="{}"]"#, selector, name, value)
+    })
+}
+
+/// Finds the element or appends a new one with `attributes` to `head`.
+fn find_or_append(
+    head: &web_sys::Element,
+    tag_name: &str,
+    attributes: &[(&str, &str)],
+) -> web_sys::Element {
+    if let Some(element) = head
+        .query_selector(&selector(tag_name, attributes))
+        .expect("query head element")
+    {
+        return element;
+    }
+    let element =
+        document().create_element(tag_name).expect("create head element");
+    for (name, value) in attributes {
+        element.set_attribute(name, value).expect("set head element attribute");
+    }
+    head.append_child(&element).expect("append head element");
+    element
+}
+
+fn remove(
+    head: &web_sys::Element,
+    tag_name: &str,
+    attributes: &[(&str, &str)],
+) {
+    if let Ok(Some(element)) =
+        head.query_selector(&selector(tag_name, attributes))
+    {
+        element.remove();
+    }
+}
+
+// ------ HTML ------
+
+/// Elements appended to `<head>` of prerendered pages.
+pub fn head_html(page: Page, guides: &[Guide]) -> String {
+    let canonical_url = page.canonical_url(guides);
+    let mut html = String::new();
+    if let Some(url) = &canonical_url {
+        html.push_str(&format!(
+            r#"<link rel="canonical" href="{}">"#,
+            prerender::escape(url, true)
+        ));
+    }
+    for tag in meta_tags(page, canonical_url.as_deref()) {
+        if let Some(content) = tag.content {
+            html.push_str(&format!(
+                r#"<meta {}="{}" content="{}">"#,
+                tag.attribute,
+                tag.key,
+                prerender::escape(&content, true)
+            ));
+        }
+    }
+    if let Some(json_ld) = json_ld(page) {
+        html.push_str(&format!(
+            r#"<script type="{}">{}</script>"#,
+            JSON_LD_TYPE, json_ld
+        ));
+    }
+    html
+}
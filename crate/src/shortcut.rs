@@ -0,0 +1,355 @@
+use crate::Msg;
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// Max delay between keys in a sequence like `g g`.
+const SEQUENCE_TIMEOUT_MS: f64 = 1000.;
+/// Shortcuts without Ctrl or Meta are ignored when these elements are focused.
+const EXCLUDED_TARGETS: &str =
+    "input, textarea, select, details, [contenteditable]";
+const MODIFIER_KEYS: &[&str] = &["Control", "Alt", "Shift", "Meta"];
+
+// ------ Action ------
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum Action {
+    PreviousGuide,
+    NextGuide,
+    ScrollToTop,
+    FocusSearch,
+    PreviousSearchMatch,
+    NextSearchMatch,
+    ToggleMode,
+    ToggleCommandPalette,
+    ToggleShortcutHelp,
+    CloseOverlays,
+}
+
+impl Action {
+    pub const ALL: &'static [Self] = &[
+        Self::PreviousGuide,
+        Self::NextGuide,
+        Self::ScrollToTop,
+        Self::FocusSearch,
+        Self::PreviousSearchMatch,
+        Self::NextSearchMatch,
+        Self::ToggleMode,
+        Self::ToggleCommandPalette,
+        Self::ToggleShortcutHelp,
+        Self::CloseOverlays,
+    ];
+
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::PreviousGuide => "Go to the previous guide",
+            Self::NextGuide => "Go to the next guide",
+            Self::ScrollToTop => "Scroll to the top",
+            Self::FocusSearch => "Focus search",
+            Self::PreviousSearchMatch => "Previous search match",
+            Self::NextSearchMatch => "Next search match",
+            Self::ToggleMode => "Switch color mode",
+            Self::ToggleCommandPalette => "Open command palette",
+            Self::ToggleShortcutHelp => "Show keyboard shortcuts",
+            Self::CloseOverlays => "Close dialogs",
+        }
+    }
+
+    /// Alternatives are separated by `,`, keys in a sequence by a space.
+    pub const fn default_bindings(self) -> &'static str {
+        match self {
+            Self::PreviousGuide => "ArrowLeft",
+            Self::NextGuide => "ArrowRight",
+            Self::ScrollToTop => "g g",
+            Self::FocusSearch => "/",
+            Self::PreviousSearchMatch => "Shift+N",
+            Self::NextSearchMatch => "n",
+            Self::ToggleMode => "Shift+D",
+            Self::ToggleCommandPalette => "Ctrl+K, Meta+K",
+            Self::ToggleShortcutHelp => "?",
+            Self::CloseOverlays => "Escape",
+        }
+    }
+
+    pub const fn msg(self) -> Msg {
+        match self {
+            Self::PreviousGuide => Msg::GoToPreviousGuide,
+            Self::NextGuide => Msg::GoToNextGuide,
+            Self::ScrollToTop => Msg::ScrollToTop,
+            Self::FocusSearch => Msg::FocusSearch,
+            Self::PreviousSearchMatch => Msg::PreviousSearchMatch,
+            Self::NextSearchMatch => Msg::NextSearchMatch,
+            Self::ToggleMode => Msg::ToggleMode,
+            Self::ToggleCommandPalette => Msg::ToggleCommandPalette,
+            Self::ToggleShortcutHelp => Msg::ToggleShortcutHelp,
+            Self::CloseOverlays => Msg::CloseOverlays,
+        }
+    }
+}
+
+// ------ KeyChord ------
+
+/// A key with modifiers, e.g. `Ctrl+K`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    key: String,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl KeyChord {
+    fn from_event(event: &web_sys::KeyboardEvent) -> Self {
+        Self::new(
+            &event.key(),
+            event.ctrl_key(),
+            event.alt_key(),
+            event.shift_key(),
+            event.meta_key(),
+        )
+    }
+
+    fn new(key: &str, ctrl: bool, alt: bool, shift: bool, meta: bool) -> Self {
+        let mut chars = key.chars();
+        let single_char = chars.next().filter(|_| chars.next().is_none());
+        Self {
+            key: single_char.map_or_else(
+                || key.to_owned(),
+                |character| character.to_lowercase().collect(),
+            ),
+            ctrl,
+            alt,
+            // Shift is already applied to printable chars like `?`.
+            shift: shift
+                && single_char.map_or(true, |character| character.is_alphabetic()),
+            meta,
+        }
+    }
+
+    /// Parses chords like `Ctrl+Shift+K`, `ArrowLeft` or `?`.
+    fn parse(text: &str) -> Option<Self> {
+        let (modifiers, key) = match text.rfind('+') {
+            // The `+` key itself.
+            Some(index) if index == text.len() - 1 => {
+                (text[..index].trim_end_matches('+'), "+")
+            },
+            Some(index) => (&text[..index], &text[index + 1..]),
+            None => ("", text),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        let (mut ctrl, mut alt, mut shift, mut meta) = (false, false, false, false);
+        for modifier in modifiers.split('+').filter(|part| !part.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => ctrl = true,
+                "alt" | "option" => alt = true,
+                "shift" => shift = true,
+                "meta" | "cmd" | "command" | "super" => meta = true,
+                _ => return None,
+            }
+        }
+        Some(Self::new(key, ctrl, alt, shift, meta))
+    }
+
+    const fn has_command_modifier(&self) -> bool {
+        self.ctrl || self.meta
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let modifiers = [
+            (self.ctrl, "Ctrl+"),
+            (self.alt, "Alt+"),
+            (self.shift, "Shift+"),
+            (self.meta, "Meta+"),
+        ];
+        for (enabled, modifier) in &modifiers {
+            if *enabled {
+                f.write_str(modifier)?;
+            }
+        }
+        if self.key.chars().count() == 1 && self.shift {
+            f.write_str(&self.key.to_uppercase())
+        } else {
+            f.write_str(&self.key)
+        }
+    }
+}
+
+// ------ KeySequence ------
+
+/// Chords pressed one after another, e.g. `g g`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeySequence(Vec<KeyChord>);
+
+impl KeySequence {
+    fn parse(text: &str) -> Option<Self> {
+        let chords = text
+            .split_whitespace()
+            .map(KeyChord::parse)
+            .collect::<Option<Vec<_>>>()?;
+        if chords.is_empty() {
+            None
+        } else {
+            Some(Self(chords))
+        }
+    }
+
+    fn starts_with(&self, chords: &[KeyChord]) -> bool {
+        self.0.starts_with(chords)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chords =
+            self.0.iter().map(ToString::to_string).collect::<Vec<_>>();
+        f.write_str(&chords.join(" "))
+    }
+}
+
+/// Parses alternatives like `Ctrl+K, Meta+K`.
+pub fn parse_bindings(text: &str) -> Option<Vec<KeySequence>> {
+    let bindings = text
+        .split(',')
+        .map(str::trim)
+        .filter(|binding| !binding.is_empty())
+        .map(KeySequence::parse)
+        .collect::<Option<Vec<_>>>()?;
+    if bindings.is_empty() {
+        None
+    } else {
+        Some(bindings)
+    }
+}
+
+// ------ Shortcuts ------
+
+/// Registry of key bindings with user overrides.
+pub struct Shortcuts {
+    bindings: Vec<(Action, Vec<KeySequence>)>,
+    overrides: BTreeMap<Action, String>,
+    pending_chords: Vec<KeyChord>,
+    last_key_time: f64,
+}
+
+impl Shortcuts {
+    /// Invalid overrides are ignored and default bindings are used instead.
+    pub fn new(overrides: BTreeMap<Action, String>) -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|action| {
+                let bindings = overrides
+                    .get(action)
+                    .and_then(|binding| parse_bindings(binding))
+                    .or_else(|| parse_bindings(action.default_bindings()))
+                    .unwrap_or_default();
+                (*action, bindings)
+            })
+            .collect();
+        Self {
+            bindings,
+            overrides,
+            pending_chords: Vec::new(),
+            last_key_time: 0.,
+        }
+    }
+
+    pub const fn overrides(&self) -> &BTreeMap<Action, String> {
+        &self.overrides
+    }
+
+    pub fn bindings_text(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(bound_action, _)| *bound_action == action)
+            .map(|(_, sequences)| {
+                sequences
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns `false` if `binding` cannot be parsed.
+    pub fn set_override(&mut self, action: Action, binding: &str) -> bool {
+        if parse_bindings(binding).is_none() {
+            return false;
+        }
+        let mut overrides = std::mem::take(&mut self.overrides);
+        overrides.insert(action, binding.to_owned());
+        *self = Self::new(overrides);
+        true
+    }
+
+    pub fn reset_override(&mut self, action: Action) {
+        let mut overrides = std::mem::take(&mut self.overrides);
+        overrides.remove(&action);
+        *self = Self::new(overrides);
+    }
+
+    /// Returns the action bound to the pressed key (sequence), if any.
+    pub fn handle(&mut self, event: &web_sys::KeyboardEvent) -> Option<Action> {
+        if MODIFIER_KEYS.contains(&event.key().as_str()) {
+            return None;
+        }
+        let chord = KeyChord::from_event(event);
+        if !chord.has_command_modifier() && is_excluded_target(event) {
+            self.pending_chords.clear();
+            return None;
+        }
+
+        let time = event.time_stamp();
+        if time - self.last_key_time > SEQUENCE_TIMEOUT_MS {
+            self.pending_chords.clear();
+        }
+        self.last_key_time = time;
+
+        self.pending_chords.push(chord.clone());
+        if let Some(action) = self.match_pending_chords() {
+            return action;
+        }
+        // The new chord may start another sequence.
+        self.pending_chords = vec![chord];
+        self.match_pending_chords().flatten()
+    }
+
+    /// - `Some(Some(action))` - a binding matches.
+    /// - `Some(None)` - pending chords are a prefix of a longer binding.
+    /// - `None` - nothing matches.
+    fn match_pending_chords(&mut self) -> Option<Option<Action>> {
+        let mut is_prefix = false;
+        for (action, sequences) in &self.bindings {
+            for sequence in sequences {
+                if sequence.0 == self.pending_chords {
+                    let action = *action;
+                    self.pending_chords.clear();
+                    return Some(Some(action));
+                }
+                is_prefix |= sequence.starts_with(&self.pending_chords);
+            }
+        }
+        if is_prefix {
+            Some(None)
+        } else {
+            None
+        }
+    }
+}
+
+fn is_excluded_target(event: &web_sys::KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .map_or(false, |element| {
+            element.is_content_editable()
+                || matches!(element.closest(EXCLUDED_TARGETS), Ok(Some(_)))
+        })
+}
@@ -0,0 +1,73 @@
+//! `sitemap.xml` and `robots.txt` written by `src/bin/prerender.rs`.
+
+use crate::{
+    changelog, guide, prerender::href, Urls, DEFAULT_SEED_VERSION,
+    SEARCH_PATH_PART, SITE_URL,
+};
+use seed::prelude::*;
+
+const HOME_PRIORITY: &str = "1.0";
+const DEFAULT_VERSION_PRIORITY: &str = "0.8";
+const OLD_VERSION_PRIORITY: &str = "0.2";
+const CHANGELOG_PRIORITY: &str = "0.5";
+const GLOSSARY_PRIORITY: &str = "0.5";
+
+/// The home page, the changelog, the glossary and guides with canonical URLs.
+/// Old guides with a newer equivalent are left out,
+/// because their canonical URL points to it (see `Page::canonical_url`).
+/// Guides of `DEFAULT_SEED_VERSION` have a higher priority than old ones.
+pub fn sitemap_xml() -> String {
+    let base_url = Url::new();
+    let guides = guide::guides();
+    let guide_entries = guides
+        .iter()
+        .filter(|guide| {
+            let latest = guide::latest_equivalent(&guides, guide);
+            latest.seed_version == guide.seed_version
+        })
+        .map(|guide| {
+            let priority =
+                if guide.seed_version == DEFAULT_SEED_VERSION.version() {
+                    DEFAULT_VERSION_PRIORITY
+                } else {
+                    OLD_VERSION_PRIORITY
+                };
+            entry(&href(&Urls::new(&base_url).guide(guide)), priority)
+        });
+    let entries = vec![
+        entry(&href(&Urls::new(&base_url).home()), HOME_PRIORITY),
+        entry(
+            &href(
+                &Urls::new(&base_url).changelog(changelog::Filter::default()),
+            ),
+            CHANGELOG_PRIORITY,
+        ),
+        entry(&href(&Urls::new(&base_url).glossary()), GLOSSARY_PRIORITY),
+    ]
+    .into_iter()
+    .chain(guide_entries)
+    .collect::<String>();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+{}</urlset>
+"#,
+        entries
+    )
+}
+
+fn entry(path: &str, priority: &str) -> String {
+    format!(
+        "  <url><loc>{}{}</loc><priority>{}</priority></url>\n",
+        SITE_URL, path, priority
+    )
+}
+
+/// Search results are generated for any query, so they aren't crawled.
+pub fn robots_txt() -> String {
+    format!(
+        "User-agent: *\nDisallow: /{}\n\nSitemap: {}/sitemap.xml\n",
+        SEARCH_PATH_PART, SITE_URL
+    )
+}
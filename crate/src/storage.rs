@@ -0,0 +1,208 @@
+use seed::{prelude::*, *};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::convert::TryFrom;
+
+/// Migrates stored JSON from one version to the next one.
+pub type Migration = fn(&mut Map<String, Value>);
+
+/// Loads the value stored under `key` and migrates it to `current_version`.
+/// `migrations[i]` migrates data from version `i + 1` to `i + 2`.
+/// Data without `version` are version 1.
+///
+/// An invalid value is logged and moved to `{key}_backup` so it isn't lost.
+pub fn load<T: DeserializeOwned + Default>(
+    key: &str,
+    current_version: u64,
+    migrations: &[Migration],
+) -> T {
+    let stored_value: web_storage::Result<Value> = LocalStorage::get(key);
+    let stored_value = match stored_value {
+        Ok(stored_value) => stored_value,
+        Err(web_storage::WebStorageError::KeyNotFoundError) => {
+            return T::default()
+        },
+        Err(error) => {
+            error!("Stored value can't be read", key, error);
+            return T::default();
+        },
+    };
+
+    let (value, backup) =
+        load_stored(key, stored_value, current_version, migrations);
+    if let Some(backup) = backup {
+        error!(
+            "Stored value can't be loaded, see local storage key",
+            backup.key,
+            backup.error
+        );
+        if let Err(error) = LocalStorage::insert(&backup.key, &backup.value) {
+            error!("Backup can't be saved", error);
+        }
+    }
+    value
+}
+
+/// The stored value that can't be loaded, to be saved under `key`.
+struct Backup {
+    key: String,
+    value: Value,
+    error: String,
+}
+
+/// The migrated value, or the default one and the backup of `stored_value`.
+fn load_stored<T: DeserializeOwned + Default>(
+    key: &str,
+    stored_value: Value,
+    current_version: u64,
+    migrations: &[Migration],
+) -> (T, Option<Backup>) {
+    match from_json(stored_value.clone(), current_version, migrations) {
+        Ok(value) => (value, None),
+        Err(error) => (
+            T::default(),
+            Some(Backup {
+                key: format!("{}_backup", key),
+                value: stored_value,
+                error,
+            }),
+        ),
+    }
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    LocalStorage::insert(key, value).expect("insert to local storage");
+}
+
+/// Migrates JSON (e.g. imported from a file) and deserializes it.
+pub fn from_json<T: DeserializeOwned>(
+    value: Value,
+    current_version: u64,
+    migrations: &[Migration],
+) -> Result<T, String> {
+    let value = migrate(value, current_version, migrations)?;
+    serde_json::from_value(value).map_err(|error| error.to_string())
+}
+
+fn migrate(
+    value: Value,
+    current_version: u64,
+    migrations: &[Migration],
+) -> Result<Value, String> {
+    let mut object = match value {
+        Value::Object(object) => object,
+        _ => return Err("stored value is not a JSON object".to_owned()),
+    };
+    let version = match object.get("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .filter(|version| *version > 0)
+            .ok_or_else(|| format!("invalid version: {}", version))?,
+    };
+    // Data from a newer app version are used as they are.
+    // Unknown fields are ignored and missing ones fail the deserialization.
+    let applied_migrations =
+        usize::try_from(version - 1).unwrap_or(usize::MAX);
+    for migration in migrations.iter().skip(applied_migrations) {
+        migration(&mut object);
+    }
+    if version < current_version {
+        object.insert("version".to_owned(), current_version.into());
+    }
+    Ok(Value::Object(object))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Default, PartialEq, Deserialize)]
+    struct Data {
+        version: u64,
+        items: Vec<String>,
+        limit: u64,
+    }
+
+    const MIGRATIONS: &[Migration] = &[add_items, add_limit];
+
+    fn add_items(data: &mut Map<String, Value>) {
+        data.insert("items".to_owned(), json!([]));
+    }
+
+    fn add_limit(data: &mut Map<String, Value>) {
+        data.insert("limit".to_owned(), json!(10));
+    }
+
+    #[test]
+    fn from_json_migrates_data_without_version_from_version_1() {
+        let data: Data = from_json(json!({}), 3, MIGRATIONS).unwrap();
+        assert_eq!(
+            data,
+            Data {
+                version: 3,
+                items: Vec::new(),
+                limit: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn from_json_applies_only_newer_migrations() {
+        let data: Data =
+            from_json(json!({ "version": 2, "items": ["a"] }), 3, MIGRATIONS)
+                .unwrap();
+        assert_eq!(
+            data,
+            Data {
+                version: 3,
+                items: vec!["a".to_owned()],
+                limit: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn from_json_keeps_data_from_newer_version() {
+        let data: Data = from_json(
+            json!({ "version": 4, "items": [], "limit": 5, "unknown": true }),
+            3,
+            MIGRATIONS,
+        )
+        .unwrap();
+        assert_eq!(data.version, 4);
+        assert_eq!(data.limit, 5);
+    }
+
+    #[test]
+    fn from_json_rejects_invalid_data() {
+        assert!(from_json::<Data>(json!([]), 3, MIGRATIONS).is_err());
+        assert!(
+            from_json::<Data>(json!({ "version": 0 }), 3, MIGRATIONS).is_err()
+        );
+        assert!(from_json::<Data>(json!({ "version": "2" }), 3, MIGRATIONS)
+            .is_err());
+    }
+
+    #[test]
+    fn load_stored_has_no_backup_for_valid_value() {
+        let (data, backup) =
+            load_stored::<Data>("data", json!({}), 3, MIGRATIONS);
+        assert_eq!(data.limit, 10);
+        assert!(backup.is_none());
+    }
+
+    #[test]
+    fn load_stored_backs_up_invalid_value() {
+        let stored_value = json!({ "version": 3, "items": "a" });
+        let (data, backup) =
+            load_stored::<Data>("data", stored_value.clone(), 3, MIGRATIONS);
+        assert_eq!(data, Data::default());
+        let backup = backup.unwrap();
+        assert_eq!(backup.key, "data_backup");
+        assert_eq!(backup.value, stored_value);
+        assert!(!backup.error.is_empty());
+    }
+}
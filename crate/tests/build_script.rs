@@ -0,0 +1,9 @@
+//! Host tests of `build.rs` modules that don't depend on the build script.
+
+// Only the tested items are used.
+#![allow(dead_code)]
+
+#[path = "../build/date.rs"]
+mod date;
+#[path = "../build/highlight.rs"]
+mod highlight;
@@ -1,7 +1,9 @@
 import "../css/styles.css";
-import "../web_components/code-block.ts";
 
 (async () => {
   // Note: files in `crate/pkg/` will be created on the first build.
   await import("../crate/pkg/index");
+  // `code-block` is defined after the app has taken over the prerendered page,
+  // because it would replace the plain code of prerendered `<code-block>`s.
+  await import("../web_components/code-block.ts");
 })();
@@ -0,0 +1,116 @@
+// Service worker for offline reading.
+// `configs/webpack.config.js` replaces `__CACHE_VERSION__` and `__APP_SHELL__`
+// and emits this file as `/service-worker.js`.
+// The app sends guide URLs to precache (see `crate/src/offline.rs`).
+
+const CACHE_VERSION = "__CACHE_VERSION__";
+const APP_SHELL = __APP_SHELL__;
+
+const SHELL_CACHE = `shell-${CACHE_VERSION}`;
+const GUIDE_CACHE = `guides-${CACHE_VERSION}`;
+const SHELL_URL = "/index.html";
+
+self.addEventListener("install", event => {
+  event.waitUntil((async () => {
+    const cache = await caches.open(SHELL_CACHE);
+    await cache.addAll(APP_SHELL);
+    // The new version waits until the app asks it to skip waiting,
+    // so assets of the open tabs aren't replaced under their hands.
+    // The app shows the reload prompt when the worker is installed.
+  })());
+});
+
+self.addEventListener("activate", event => {
+  event.waitUntil((async () => {
+    const cacheNames = await caches.keys();
+    await Promise.all(
+      cacheNames
+        .filter(name => name !== SHELL_CACHE && name !== GUIDE_CACHE)
+        .map(name => caches.delete(name))
+    );
+    await self.clients.claim();
+  })());
+});
+
+self.addEventListener("message", event => {
+  const message = event.data || {};
+  switch (message.type) {
+    case "precache-guides":
+      event.waitUntil(precacheGuides(message.urls));
+      break;
+    case "skip-waiting":
+      self.skipWaiting();
+      break;
+  }
+});
+
+self.addEventListener("fetch", event => {
+  const request = event.request;
+  const url = new URL(request.url);
+  if (request.method !== "GET" || url.origin !== self.location.origin) {
+    return;
+  }
+  if (request.mode === "navigate") {
+    event.respondWith(networkFirst(request));
+  } else {
+    event.respondWith(cacheFirst(request));
+  }
+});
+
+// Pages are always fetched when online so a new deployment isn't hidden
+// behind stale HTML. The cached page or the app shell is the offline fallback.
+async function networkFirst(request) {
+  const guideCache = await caches.open(GUIDE_CACHE);
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      await guideCache.put(request, response.clone());
+    }
+    return response;
+  } catch (error) {
+    return (await guideCache.match(request, { ignoreSearch: true }))
+      || (await caches.match(SHELL_URL))
+      || Response.error();
+  }
+}
+
+// Other assets have hashed names or change only with a new deployment.
+async function cacheFirst(request) {
+  return (await caches.match(request)) || fetch(request);
+}
+
+// Caches the guide pages and removes guides of unselected versions.
+async function precacheGuides(urls) {
+  const cache = await caches.open(GUIDE_CACHE);
+  const wantedUrls = new Set(urls.map(url => new URL(url, self.location.origin).href));
+  for (const request of await cache.keys()) {
+    if (!wantedUrls.has(request.url)) {
+      await cache.delete(request);
+    }
+  }
+
+  let cached = 0;
+  let failed = false;
+  await postToClients({ type: "precache-progress", cached, total: urls.length });
+  for (const url of urls) {
+    try {
+      if (!(await cache.match(url))) {
+        await cache.add(url);
+      }
+      cached += 1;
+      await postToClients({ type: "precache-progress", cached, total: urls.length });
+    } catch (error) {
+      failed = true;
+    }
+  }
+  if (failed) {
+    await postToClients({ type: "precache-failed" });
+  }
+}
+
+async function postToClients(message) {
+  const clients = await self.clients.matchAll({ type: "window", includeUncontrolled: true });
+  for (const client of clients) {
+    client.postMessage(message);
+  }
+}
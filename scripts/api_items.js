@@ -0,0 +1,146 @@
+const fs = require('fs');
+
+// Writes public items of a crate version to `crate/api_items/<version>.txt`
+// for `crate/build.rs`, which links them in guides to docs.rs.
+//
+// One item per line: `path<TAB>docs.rs page`, e.g.
+// `app::orders::Orders::after_next_render	seed/app/orders/trait.Orders.html#method.after_next_render`
+//
+// The input is rustdoc JSON of the published crate:
+// cargo +nightly rustdoc --lib -- -Z unstable-options --output-format json
+//
+// Usage: node scripts/api_items.js target/doc/seed.json 0.8.0
+
+const [jsonPath, version] = process.argv.slice(2);
+if (!jsonPath || !version) {
+    console.error('Usage: node scripts/api_items.js <rustdoc JSON> <version>');
+    process.exit(1);
+}
+
+const PAGE_PREFIXES = {
+    struct: 'struct',
+    enum: 'enum',
+    union: 'union',
+    trait: 'trait',
+    function: 'fn',
+    macro: 'macro',
+    proc_macro: 'derive',
+    type_alias: 'type',
+    constant: 'constant',
+    static: 'static',
+};
+
+const doc = JSON.parse(fs.readFileSync(jsonPath, 'utf8'));
+const crateName = doc.index[doc.root].name;
+const lines = new Set();
+
+// Items are documented where they are defined if their module is public,
+// otherwise where they are re-exported.
+const pages = new Map();
+const modules = [{ id: doc.root, path: [] }];
+const reexports = [];
+while (modules.length > 0) {
+    const { id, path } = modules.shift();
+    for (const childId of doc.index[id].inner.module.items) {
+        const child = doc.index[childId];
+        if (child.visibility !== 'public') {
+            continue;
+        }
+        const kind = Object.keys(child.inner)[0];
+        if (kind === 'use') {
+            reexports.push({ use: child.inner.use, path });
+        } else if (kind === 'module') {
+            if (!child.inner.module.is_stripped) {
+                modules.push({ id: childId, path: [...path, child.name] });
+            }
+        } else if (!pages.has(childId)) {
+            pages.set(childId, [...path, child.name]);
+        }
+    }
+    // Re-exports of private items are processed after public modules.
+    while (modules.length === 0 && reexports.length > 0) {
+        const { use, path } = reexports.shift();
+        const target = doc.index[use.id];
+        if (!target || target.crate_id !== 0) {
+            continue;
+        }
+        const kind = Object.keys(target.inner)[0];
+        if (kind === 'module') {
+            if (target.inner.module.is_stripped || use.is_glob) {
+                modules.push({
+                    id: use.id,
+                    path: use.is_glob ? path : [...path, use.name],
+                });
+            }
+        } else if (!pages.has(use.id)) {
+            pages.set(use.id, [...path, use.name]);
+        }
+    }
+}
+
+for (const [id, path] of pages) {
+    const item = doc.index[id];
+    const kind = Object.keys(item.inner)[0];
+    const prefix = PAGE_PREFIXES[kind];
+    if (!prefix) {
+        continue;
+    }
+    const page = [crateName, ...path.slice(0, -1), `${prefix}.${path[path.length - 1]}.html`].join('/');
+    lines.add(`${path.join('::')}\t${page}`);
+    for (const [name, anchor] of members(item, kind)) {
+        lines.add(`${path.join('::')}::${name}\t${page}#${anchor}`);
+    }
+}
+
+/**
+ * Methods, associated items and variants with their anchors on the item page.
+ *
+ * @returns Array of `[name, anchor]`
+ */
+function members(item, kind) {
+    const members = [];
+    if (kind === 'trait') {
+        for (const memberId of item.inner.trait.items) {
+            members.push(member(doc.index[memberId], true));
+        }
+    }
+    if (kind === 'enum') {
+        for (const variantId of item.inner.enum.variants) {
+            members.push([doc.index[variantId].name, `variant.${doc.index[variantId].name}`]);
+        }
+    }
+    const impls = (item.inner[kind] && item.inner[kind].impls) || [];
+    for (const implId of impls) {
+        const implementation = doc.index[implId].inner.impl;
+        if (implementation.trait !== null) {
+            continue;
+        }
+        for (const memberId of implementation.items) {
+            const implItem = doc.index[memberId];
+            if (implItem.visibility === 'public') {
+                members.push(member(implItem, false));
+            }
+        }
+    }
+    return members.filter(Boolean);
+}
+
+function member(item, inTrait) {
+    const kind = Object.keys(item.inner)[0];
+    switch (kind) {
+        case 'function':
+            return [item.name, `${inTrait && !item.inner.function.has_body ? 'tymethod' : 'method'}.${item.name}`];
+        case 'assoc_const':
+            return [item.name, `associatedconstant.${item.name}`];
+        case 'assoc_type':
+            return [item.name, `associatedtype.${item.name}`];
+        default:
+            return null;
+    }
+}
+
+const header = `# Public items of ${crateName} ${version} (see \`scripts/api_items.js\`).\n`;
+fs.writeFileSync(
+    `crate/api_items/${version}.txt`,
+    header + [...lines].sort().join('\n') + '\n'
+);
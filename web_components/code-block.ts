@@ -1,10 +1,14 @@
-import { LitElement, html, property, customElement } from 'lit-element';
+import { LitElement, html, property, customElement, PropertyValues } from 'lit-element';
 import { unsafeHTML } from 'lit-html/directives/unsafe-html';
 
 @customElement('code-block')
 export class CodeBlock extends LitElement {
   @property() lang = '';
   @property() code = '';
+  // Seed items linked to docs.rs by `build.rs` as `[[start, end, "url"]]`.
+  @property() links = '';
+  // Search matches marked by the app as `[[start, end, "id", "class"]]`.
+  @property() marks = '';
 
   render() {
     const highlightedCode = highlightCode(this.code, this.lang)
@@ -12,6 +16,23 @@ export class CodeBlock extends LitElement {
     return html`<pre><code>${unsafeHTML(highlightedCode)}</code></pre>`;
   }
 
+  updated(changedProperties: PropertyValues) {
+    if (
+      !changedProperties.has('code')
+      && !changedProperties.has('links')
+      && !changedProperties.has('marks')
+    ) {
+      return;
+    }
+    const codeElement = this.querySelector('code');
+    if (codeElement) {
+      unwrap(codeElement, 'mark');
+      unwrap(codeElement, 'a.api-link');
+      addLinks(codeElement, this.links ? JSON.parse(this.links) : []);
+      addMarks(codeElement, this.marks ? JSON.parse(this.marks) : []);
+    }
+  }
+
   createRenderRoot() {
     return this;
   }
@@ -24,3 +45,65 @@ function highlightCode(code: string, lang: string): string {
     .highlightAuto(code, lang ? [lang] : undefined)
     .value;
 }
+
+// Highlighted tokens split the code into text nodes,
+// so a range may be wrapped in parts.
+function wrapRanges(
+  codeElement: HTMLElement,
+  ranges: Array<[number, number]>,
+  createWrapper: (rangeIndex: number, isFirstPart: boolean) => HTMLElement,
+) {
+  const textNodes: Array<[Text, number]> = [];
+  const walker = document.createTreeWalker(codeElement, NodeFilter.SHOW_TEXT);
+  let offset = 0;
+  while (walker.nextNode()) {
+    const textNode = walker.currentNode as Text;
+    textNodes.push([textNode, offset]);
+    offset += textNode.length;
+  }
+
+  for (const [textNode, nodeStart] of textNodes) {
+    const nodeEnd = nodeStart + textNode.length;
+    // The last part first, so the start of the text node stays the same.
+    const parts = ranges
+      .map((range, index) => [range, index] as [[number, number], number])
+      .filter(([[start, end]]) => start < nodeEnd && end > nodeStart)
+      .reverse();
+    for (const [[start, end], index] of parts) {
+      const part = textNode.splitText(Math.max(start, nodeStart) - nodeStart);
+      part.splitText(Math.min(end, nodeEnd) - Math.max(start, nodeStart));
+      const wrapper = createWrapper(index, start >= nodeStart);
+      part.replaceWith(wrapper);
+      wrapper.appendChild(part);
+    }
+  }
+}
+
+function addLinks(codeElement: HTMLElement, links: Array<[number, number, string]>) {
+  wrapRanges(codeElement, links.map(([start, end]) => [start, end]), index => {
+    const link = document.createElement('a');
+    link.className = 'api-link';
+    link.href = links[index][2];
+    return link;
+  });
+}
+
+// Only the first part of a match gets its id, so the app can scroll to it.
+function addMarks(codeElement: HTMLElement, marks: Array<[number, number, string, string]>) {
+  wrapRanges(codeElement, marks.map(([start, end]) => [start, end]), (index, isFirstPart) => {
+    const mark = document.createElement('mark');
+    mark.className = marks[index][3];
+    if (isFirstPart) {
+      mark.id = marks[index][2];
+    }
+    return mark;
+  });
+}
+
+// Links and marks from the previous render may stay if the code hasn't changed.
+function unwrap(codeElement: HTMLElement, selector: string) {
+  codeElement.querySelectorAll(selector).forEach(element => {
+    element.replaceWith(...Array.from(element.childNodes));
+  });
+  codeElement.normalize();
+}